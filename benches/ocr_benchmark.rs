@@ -1,13 +1,14 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use rusto::{RustO, RustOConfig};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rusto::{RapidOCR, RapidOCRConfig};
 
-fn create_ocr() -> RustO {
-    let config = RustOConfig {
+fn create_ocr() -> RapidOCR {
+    let config = RapidOCRConfig {
         det_model_path: "models/PPOCR_v5/det.onnx".to_string(),
         rec_model_path: "models/PPOCR_v5/rec.onnx".to_string(),
         dict_path: "models/PPOCR_v5/dict.txt".to_string(),
+        ..RapidOCRConfig::default()
     };
-    RustO::new(config).expect("Failed to create OCR")
+    RapidOCR::new(config).expect("Failed to create OCR")
 }
 
 fn benchmark_full_ocr(c: &mut Criterion) {
@@ -40,19 +41,56 @@ fn benchmark_full_ocr(c: &mut Criterion) {
 fn benchmark_detection_only(c: &mut Criterion) {
     let mut group = c.benchmark_group("detection_only");
     group.sample_size(20);
-    
-    // Simplified - just benchmark full OCR for now as internal modules aren't exposed
+
     if std::path::Path::new("models/images/ktp-teng.jpg").exists() {
         group.bench_function("ktp-teng", |b| {
             let mut ocr = create_ocr();
             b.iter(|| {
-                ocr.ocr(black_box("models/images/ktp-teng.jpg")).expect("OCR failed")
+                ocr.detect(black_box("models/images/ktp-teng.jpg")).expect("detection failed")
             });
         });
     }
-    
+
+    group.finish();
+}
+
+/// Throughput over the whole image set via `ocr_batch`, to demonstrate
+/// scaling across `RapidOCRConfig::num_threads` worker threads instead of
+/// just timing a single image repeatedly like `benchmark_full_ocr` does.
+fn benchmark_batch_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("full_ocr");
+
+    let test_images: Vec<&str> = vec![
+        "models/images/ktp-teng.jpg",
+        "models/test_images/example1.png",
+    ]
+    .into_iter()
+    .filter(|p| std::path::Path::new(p).exists())
+    .collect();
+
+    if test_images.is_empty() {
+        group.finish();
+        return;
+    }
+
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(test_images.len() as u64));
+    group.bench_function("batch_throughput", |b| {
+        let mut ocr = create_ocr();
+        b.iter(|| {
+            for result in ocr.ocr_batch(black_box(&test_images)) {
+                result.expect("OCR failed");
+            }
+        });
+    });
+
     group.finish();
 }
 
-criterion_group!(benches, benchmark_full_ocr, benchmark_detection_only);
+criterion_group!(
+    benches,
+    benchmark_full_ocr,
+    benchmark_detection_only,
+    benchmark_batch_throughput
+);
 criterion_main!(benches);
@@ -0,0 +1,47 @@
+//! Benchmarks the pure-Rust `warp_perspective` path on a full-page-sized
+//! image. Compare `cargo bench --bench warp_perspective_benchmark` against
+//! `cargo bench --bench warp_perspective_benchmark --features simd-warp` to
+//! see the SIMD speedup.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use image::{DynamicImage, RgbImage};
+use rusto::__bench_warp_perspective;
+
+const WIDTH: u32 = 1000;
+const HEIGHT: u32 = 1400;
+
+fn slight_perspective_matrix() -> [[f64; 3]; 3] {
+    // A mild quad-to-rectangle homography, representative of the
+    // document-rectification warps this function is used for.
+    [
+        [1.02, 0.01, -5.0],
+        [-0.01, 1.03, -8.0],
+        [0.00002, 0.00001, 1.0],
+    ]
+}
+
+fn test_image() -> DynamicImage {
+    let buf = RgbImage::from_fn(WIDTH, HEIGHT, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, ((x + y) % 256) as u8])
+    });
+    DynamicImage::ImageRgb8(buf)
+}
+
+fn benchmark_warp_perspective(c: &mut Criterion) {
+    let src = test_image();
+    let matrix = slight_perspective_matrix();
+
+    c.bench_function("warp_perspective_1000x1400", |b| {
+        b.iter(|| {
+            __bench_warp_perspective(
+                black_box(&src),
+                black_box(&matrix),
+                black_box(WIDTH as i32),
+                black_box(HEIGHT as i32),
+            )
+        });
+    });
+}
+
+criterion_group!(benches, benchmark_warp_perspective);
+criterion_main!(benches);
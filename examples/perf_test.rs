@@ -1,64 +1,190 @@
-use rusto::{RustO, RustOConfig};
-use std::time::Instant;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use rusto::{RapidOCR, RapidOCRConfig};
+use serde::{Deserialize, Serialize};
+
+/// One image's timing statistics, as recorded into (or compared against) a
+/// baseline file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ImageStats {
+    avg_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+    throughput: f64,
+}
+
+/// A full run's statistics: per-image timings plus the init time and model
+/// paths used, so a baseline file is self-describing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Baseline {
+    init_ms: f64,
+    det_model_path: String,
+    rec_model_path: String,
+    dict_path: String,
+    per_image: BTreeMap<String, ImageStats>,
+}
+
+/// Regressions flagged beyond this percentage slowdown on the mean cause
+/// `--compare` to exit non-zero, so this can gate CI.
+const DEFAULT_REGRESSION_THRESHOLD_PCT: f64 = 5.0;
 
 fn main() {
-    println!("=== RustO Performance Test ===\n");
-    
-    let config = RustOConfig {
+    let args: Vec<String> = std::env::args().collect();
+    let save_path = flag_value(&args, "--save");
+    let compare_path = flag_value(&args, "--compare");
+    let threshold_pct = flag_value(&args, "--threshold")
+        .and_then(|s| s.parse::<f64>().ok())
+        .unwrap_or(DEFAULT_REGRESSION_THRESHOLD_PCT);
+
+    println!("=== RapidOCR Performance Test ===\n");
+
+    let config = RapidOCRConfig {
         det_model_path: "models/PPOCR_v5/det.onnx".to_string(),
         rec_model_path: "models/PPOCR_v5/rec.onnx".to_string(),
         dict_path: "models/PPOCR_v5/dict.txt".to_string(),
+        ..RapidOCRConfig::default()
     };
-    
+
     println!("Initializing OCR engine...");
     let init_start = Instant::now();
-    let mut ocr = RustO::new(config).expect("Failed to create OCR");
-    println!("Initialization took: {:?}\n", init_start.elapsed());
-    
+    let mut ocr = RapidOCR::new(config.clone()).expect("Failed to create OCR");
+    let init_elapsed = init_start.elapsed();
+    println!("Initialization took: {:?}\n", init_elapsed);
+
     let test_images = vec![
         ("KTP", "models/images/ktp-teng.jpg"),
         ("Example1", "models/test_images/example1.png"),
     ];
-    
+
+    let mut per_image: BTreeMap<String, ImageStats> = BTreeMap::new();
+
     for (name, path) in &test_images {
         if !std::path::Path::new(path).exists() {
-            println!("⚠ Skipping {} - file not found: {}\n", name, path);
+            println!("Skipping {} - file not found: {}\n", name, path);
             continue;
         }
-        
+
         println!("Testing {} ({}):", name, path);
-        
-        // Warmup run
+
         println!("  Warmup run...");
         let _ = ocr.ocr(path);
-        
-        // Timed runs
+
         let num_runs = 5;
         let mut times = Vec::new();
-        
+
         for i in 1..=num_runs {
             let start = Instant::now();
             let results = ocr.ocr(path).expect("OCR failed");
             let elapsed = start.elapsed();
             times.push(elapsed);
-            
-            println!("  Run {}: {:?} - {} text regions detected", i, elapsed, results.len());
+
+            println!(
+                "  Run {}: {:?} - {} text regions detected",
+                i,
+                elapsed,
+                results.len()
+            );
             if i == 1 && !results.is_empty() {
-                println!("    Sample result: {} (score: {:.3})", 
-                         results[0].text, results[0].score);
+                println!(
+                    "    Sample result: {} (score: {:.3})",
+                    results[0].text, results[0].score
+                );
             }
         }
-        
-        // Calculate statistics
-        let total: std::time::Duration = times.iter().sum();
-        let avg = total / num_runs as u32;
-        let min = times.iter().min().unwrap();
-        let max = times.iter().max().unwrap();
-        
+
+        let stats = compute_stats(&times);
         println!("\n  Statistics:");
-        println!("    Average: {:?}", avg);
-        println!("    Min: {:?}", min);
-        println!("    Max: {:?}", max);
-        println!("    Throughput: {:.2} images/sec\n", 1.0 / avg.as_secs_f64());
+        println!("    Average: {:.2}ms", stats.avg_ms);
+        println!("    Min: {:.2}ms", stats.min_ms);
+        println!("    Max: {:.2}ms", stats.max_ms);
+        println!("    Throughput: {:.2} images/sec\n", stats.throughput);
+
+        per_image.insert(name.to_string(), stats);
     }
+
+    let baseline = Baseline {
+        init_ms: init_elapsed.as_secs_f64() * 1000.0,
+        det_model_path: config.det_model_path,
+        rec_model_path: config.rec_model_path,
+        dict_path: config.dict_path,
+        per_image,
+    };
+
+    if let Some(path) = &save_path {
+        let json = serde_json::to_string_pretty(&baseline).expect("failed to serialize baseline");
+        std::fs::write(path, json).expect("failed to write baseline file");
+        println!("Saved baseline to {}", path);
+    }
+
+    if let Some(path) = &compare_path {
+        let prior_json = std::fs::read_to_string(path).expect("failed to read baseline file");
+        let prior: Baseline = serde_json::from_str(&prior_json).expect("failed to parse baseline file");
+        let regressed = compare_baselines(&prior, &baseline, threshold_pct);
+        if regressed {
+            eprintln!(
+                "\nRegression detected: one or more images slowed down by more than {:.1}%",
+                threshold_pct
+            );
+            std::process::exit(1);
+        }
+        println!("\nNo regressions beyond {:.1}% threshold.", threshold_pct);
+    }
+}
+
+fn compute_stats(times: &[Duration]) -> ImageStats {
+    let total: Duration = times.iter().sum();
+    let avg = total / times.len() as u32;
+    let min = *times.iter().min().unwrap();
+    let max = *times.iter().max().unwrap();
+
+    ImageStats {
+        avg_ms: avg.as_secs_f64() * 1000.0,
+        min_ms: min.as_secs_f64() * 1000.0,
+        max_ms: max.as_secs_f64() * 1000.0,
+        throughput: 1.0 / avg.as_secs_f64(),
+    }
+}
+
+/// Print a per-image percent-change table (keyed by image name, so renamed
+/// or added/removed images are reported rather than silently skipped) and
+/// return `true` if any image's mean regressed beyond `threshold_pct`.
+fn compare_baselines(prior: &Baseline, current: &Baseline, threshold_pct: f64) -> bool {
+    println!("\n=== Comparison against baseline ===");
+    let mut regressed = false;
+
+    for (name, current_stats) in &current.per_image {
+        match prior.per_image.get(name) {
+            Some(prior_stats) => {
+                let pct_change =
+                    (current_stats.avg_ms - prior_stats.avg_ms) / prior_stats.avg_ms * 100.0;
+                let flag = if pct_change > threshold_pct { " REGRESSION" } else { "" };
+                println!(
+                    "  {}: {:.2}ms -> {:.2}ms ({:+.1}%){}",
+                    name, prior_stats.avg_ms, current_stats.avg_ms, pct_change, flag
+                );
+                if pct_change > threshold_pct {
+                    regressed = true;
+                }
+            }
+            None => {
+                println!("  {}: no prior baseline entry (new image)", name);
+            }
+        }
+    }
+
+    for name in prior.per_image.keys() {
+        if !current.per_image.contains_key(name) {
+            println!("  {}: present in baseline but missing from this run", name);
+        }
+    }
+
+    regressed
+}
+
+fn flag_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
 }
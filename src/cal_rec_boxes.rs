@@ -3,6 +3,7 @@ use opencv::core::Point2f;
 #[cfg(not(feature = "use-opencv"))]
 use crate::image_impl::Point2f;
 
+use crate::geometry::Quad;
 use crate::rec::{TextRecOutput, WordInfo, WordType};
 
 pub struct CalRecBoxes;
@@ -14,7 +15,7 @@ impl CalRecBoxes {
 
     pub fn calc_word_boxes(
         &self,
-        dt_boxes: &[[Point2f; 4]],
+        dt_boxes: &[Quad],
         rec_res: &TextRecOutput,
         return_single_char_box: bool,
     ) -> Vec<Vec<(String, f32, [Point2f; 4])>> {
@@ -0,0 +1,252 @@
+use std::time::Instant;
+
+use ndarray::{Array3, Array4, Ix2};
+
+#[cfg(feature = "use-opencv")]
+use opencv::{core, imgproc, prelude::*};
+
+#[cfg(feature = "use-opencv")]
+use opencv::core::Mat;
+
+#[cfg(not(feature = "use-opencv"))]
+use crate::image_impl::{self, Mat, Size, INTER_LINEAR, ROTATE_180};
+
+use crate::engine::{EngineError, MnnSession};
+use crate::types::ClsConfig;
+
+pub struct ClsOutput {
+    pub imgs: Vec<Mat>,
+    pub angles: Vec<String>,
+    pub scores: Vec<f32>,
+    pub elapse: f64,
+}
+
+/// Text-direction classifier, mirroring `TextDetector`/`TextRecognizer`.
+///
+/// Corrects upside-down text crops (classified as the "180" label) before
+/// they reach `TextRecognizer`.
+pub struct TextClassifier {
+    pub cfg: ClsConfig,
+    pub session: MnnSession,
+}
+
+impl TextClassifier {
+    pub fn new(cfg: ClsConfig) -> Result<Self, EngineError> {
+        let session = MnnSession::from_cls_config(&cfg)?;
+        Ok(Self { cfg, session })
+    }
+
+    pub fn run(&mut self, imgs: &[Mat]) -> Result<ClsOutput, EngineError> {
+        let start = Instant::now();
+
+        if imgs.is_empty() {
+            return Ok(ClsOutput {
+                imgs: Vec::new(),
+                angles: Vec::new(),
+                scores: Vec::new(),
+                elapse: 0.0,
+            });
+        }
+
+        let mut img_list: Vec<Mat> = imgs.to_vec();
+
+        // Sort by aspect ratio so batches pad to similar widths, same trick
+        // `TextRecognizer::run` uses.
+        let mut indices: Vec<usize> = (0..img_list.len()).collect();
+        indices.sort_by(|&a, &b| {
+            let ratio_a = img_list[a].cols() as f32 / img_list[a].rows().max(1) as f32;
+            let ratio_b = img_list[b].cols() as f32 / img_list[b].rows().max(1) as f32;
+            ratio_a.partial_cmp(&ratio_b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let img_num = img_list.len();
+        let batch_num = self.cfg.cls_batch_num as usize;
+
+        let mut all_angles: Vec<String> = vec![String::new(); img_num];
+        let mut all_scores: Vec<f32> = vec![0.0; img_num];
+
+        let (img_c, img_h, img_w) = (
+            self.cfg.cls_image_shape[0] as usize,
+            self.cfg.cls_image_shape[1] as usize,
+            self.cfg.cls_image_shape[2] as usize,
+        );
+
+        let mut beg = 0usize;
+        while beg < img_num {
+            let end = (beg + batch_num).min(img_num);
+
+            let mut norm_batch: Vec<Array3<f32>> = Vec::with_capacity(end - beg);
+            for &idx in &indices[beg..end] {
+                let norm = self.resize_norm_img(&img_list[idx], img_c, img_h, img_w)?;
+                norm_batch.push(norm);
+            }
+
+            let n = norm_batch.len();
+            let mut batch = Array4::<f32>::zeros((n, img_c, img_h, img_w));
+            for (i, arr) in norm_batch.into_iter().enumerate() {
+                batch.slice_mut(ndarray::s![i, .., .., ..]).assign(&arr);
+            }
+
+            let preds_dyn = self.session.run(batch.into_dyn())?;
+            let preds = preds_dyn
+                .into_dimensionality::<Ix2>()
+                .map_err(|_| EngineError::InvalidInputShape)?;
+
+            for (local_idx, row) in preds.outer_iter().enumerate() {
+                let mut best_idx = 0usize;
+                let mut best_val = f32::MIN;
+                for (ci, &v) in row.iter().enumerate() {
+                    if v > best_val {
+                        best_val = v;
+                        best_idx = ci;
+                    }
+                }
+
+                let actual_idx = indices[beg + local_idx];
+                let label = self
+                    .cfg
+                    .label_list
+                    .get(best_idx)
+                    .cloned()
+                    .unwrap_or_else(|| "0".to_string());
+
+                all_angles[actual_idx] = label;
+                all_scores[actual_idx] = best_val;
+            }
+
+            beg = end;
+        }
+
+        // Rotate any crop whose winning label is "180" above the threshold.
+        for (idx, (angle, score)) in all_angles.iter().zip(all_scores.iter()).enumerate() {
+            if angle == "180" && *score >= self.cfg.cls_thresh {
+                img_list[idx] = rotate_180(&img_list[idx])?;
+            }
+        }
+
+        let elapse = start.elapsed().as_secs_f64();
+
+        Ok(ClsOutput {
+            imgs: img_list,
+            angles: all_angles,
+            scores: all_scores,
+            elapse,
+        })
+    }
+
+    #[cfg(feature = "use-opencv")]
+    fn resize_norm_img(
+        &self,
+        img: &Mat,
+        img_c: usize,
+        img_h: usize,
+        img_w: usize,
+    ) -> Result<Array3<f32>, EngineError> {
+        let h = img.rows();
+        let w = img.cols();
+        if h <= 0 || w <= 0 {
+            return Err(EngineError::Preprocess("invalid image size".to_string()));
+        }
+
+        let ratio = w as f32 / h as f32;
+        let resized_w = if ((img_h as f32) * ratio).ceil() as i32 > img_w as i32 {
+            img_w as i32
+        } else {
+            ((img_h as f32) * ratio).ceil() as i32
+        };
+
+        let mut resized = Mat::default();
+        imgproc::resize(
+            img,
+            &mut resized,
+            core::Size::new(resized_w, img_h as i32),
+            0.0,
+            0.0,
+            imgproc::INTER_LINEAR,
+        )?;
+
+        let size = resized.size()?;
+        let h2 = size.height as usize;
+        let w2 = size.width as usize;
+
+        let mut out = Array3::<f32>::zeros((img_c, img_h, img_w));
+        for y in 0..h2 {
+            for x in 0..w2.min(img_w) {
+                let pix = resized.at_2d::<core::Vec3b>(y as i32, x as i32)?;
+                let b = pix[0] as f32 / 255.0;
+                let g = pix[1] as f32 / 255.0;
+                let r = pix[2] as f32 / 255.0;
+
+                out[[0, y, x]] = (b - 0.5) / 0.5;
+                out[[1, y, x]] = (g - 0.5) / 0.5;
+                out[[2, y, x]] = (r - 0.5) / 0.5;
+            }
+        }
+
+        Ok(out)
+    }
+
+    #[cfg(not(feature = "use-opencv"))]
+    fn resize_norm_img(
+        &self,
+        img: &Mat,
+        img_c: usize,
+        img_h: usize,
+        img_w: usize,
+    ) -> Result<Array3<f32>, EngineError> {
+        let h = img.rows();
+        let w = img.cols();
+        if h <= 0 || w <= 0 {
+            return Err(EngineError::Preprocess("invalid image size".to_string()));
+        }
+
+        let ratio = w as f32 / h as f32;
+        let resized_w = if ((img_h as f32) * ratio).ceil() as i32 > img_w as i32 {
+            img_w as i32
+        } else {
+            ((img_h as f32) * ratio).ceil() as i32
+        };
+
+        let mut resized = Mat::default();
+        image_impl::resize(
+            img,
+            &mut resized,
+            Size::new(resized_w, img_h as i32),
+            INTER_LINEAR,
+        )?;
+
+        let size = resized.size()?;
+        let h2 = size.height as usize;
+        let w2 = size.width as usize;
+
+        let mut out = Array3::<f32>::zeros((img_c, img_h, img_w));
+        for y in 0..h2 {
+            for x in 0..w2.min(img_w) {
+                let pix = resized.get_pixel(x as u32, y as u32);
+                let b = pix[0] as f32 / 255.0;
+                let g = pix[1] as f32 / 255.0;
+                let r = pix[2] as f32 / 255.0;
+
+                out[[0, y, x]] = (b - 0.5) / 0.5;
+                out[[1, y, x]] = (g - 0.5) / 0.5;
+                out[[2, y, x]] = (r - 0.5) / 0.5;
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "use-opencv")]
+fn rotate_180(img: &Mat) -> Result<Mat, EngineError> {
+    let mut dst = Mat::default();
+    core::rotate(img, &mut dst, core::ROTATE_180)?;
+    Ok(dst)
+}
+
+#[cfg(not(feature = "use-opencv"))]
+fn rotate_180(img: &Mat) -> Result<Mat, EngineError> {
+    let mut dst = Mat::default();
+    image_impl::rotate(img, &mut dst, ROTATE_180)?;
+    Ok(dst)
+}
@@ -14,72 +14,549 @@ impl Contour {
     pub fn new() -> Self {
         Self { points: Vec::new() }
     }
-    
+
     pub fn len(&self) -> usize {
         self.points.len()
     }
-    
+
     pub fn is_empty(&self) -> bool {
         self.points.is_empty()
     }
+
+    /// Spatial, central and normalized moments of this contour, matching
+    /// OpenCV's `cv::moments` applied to a point set (not a filled raster):
+    /// each edge of the closed contour contributes via Green's theorem, so
+    /// the result only depends on the boundary, same as `cv::moments` does
+    /// for a `vector<Point>` input.
+    pub fn moments(&self) -> Moments {
+        Moments::from_contour(&self.points)
+    }
+
+    /// Signed shoelace area in image coordinates (y growing downward):
+    /// negative for a clockwise contour, positive for counter-clockwise,
+    /// using the standard pairing of each vertex with the
+    /// next (wrapping the last back to the first). Unlike
+    /// `calculate_contour_area`, which only ever needs the magnitude, this
+    /// lets callers check winding order or reject a near-zero-area contour
+    /// with a proper `< epsilon` comparison instead of comparing an
+    /// already-`abs()`'d value.
+    pub fn signed_area(&self) -> f32 {
+        let n = self.points.len();
+        if n < 3 {
+            return 0.0;
+        }
+
+        let mut area = 0.0f32;
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let (x1, y1) = self.points[i];
+            let (x2, y2) = self.points[j];
+            area += (x1 as f32 * y2 as f32) - (x2 as f32 * y1 as f32);
+        }
+        // With y pointing down, the plain shoelace sum is positive for a
+        // clockwise contour, so flip it.
+        area * -0.5
+    }
 }
 
-/// Find contours in a binary image
-/// This matches OpenCV's findContours with RETR_LIST mode
-pub fn find_contours(binary_img: &GrayImage) -> Vec<Contour> {
+/// Spatial (`mXY`), central (`muXY`) and normalized central (`nuXY`) image
+/// moments up to third order, plus the derived Hu invariants. Field naming
+/// follows OpenCV's `cv::Moments` so callers porting PaddleOCR/OpenCV code
+/// can map fields one-to-one.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Moments {
+    pub m00: f64,
+    pub m10: f64,
+    pub m01: f64,
+    pub m20: f64,
+    pub m11: f64,
+    pub m02: f64,
+    pub m30: f64,
+    pub m21: f64,
+    pub m12: f64,
+    pub m03: f64,
+    pub mu20: f64,
+    pub mu11: f64,
+    pub mu02: f64,
+    pub mu30: f64,
+    pub mu21: f64,
+    pub mu12: f64,
+    pub mu03: f64,
+    pub nu20: f64,
+    pub nu11: f64,
+    pub nu02: f64,
+    pub nu30: f64,
+    pub nu21: f64,
+    pub nu12: f64,
+    pub nu03: f64,
+}
+
+impl Moments {
+    /// Compute moments of the closed polygon traced by `points`, using the
+    /// same edge-summation formulas as OpenCV's `contourMoments` (each edge
+    /// contributes a signed trapezoid area via the shoelace term, weighted
+    /// by polynomials in the edge endpoints for the higher-order sums).
+    /// Works for any winding order; `m00` (area) comes out negative for a
+    /// counter-clockwise contour in image coordinates (y growing downward)
+    /// and is normalized back to its absolute value, as OpenCV does.
+    pub fn from_contour(points: &[(i32, i32)]) -> Self {
+        if points.len() < 2 {
+            return Self::default();
+        }
+
+        let (mut a00, mut a10, mut a01) = (0.0, 0.0, 0.0);
+        let (mut a20, mut a11, mut a02) = (0.0, 0.0, 0.0);
+        let (mut a30, mut a21, mut a12, mut a03) = (0.0, 0.0, 0.0, 0.0);
+
+        let n = points.len();
+        let (mut xi_1, mut yi_1) = (points[n - 1].0 as f64, points[n - 1].1 as f64);
+
+        for &(px, py) in points {
+            let (xi, yi) = (px as f64, py as f64);
+
+            let xi_12 = xi_1 * xi_1;
+            let yi_12 = yi_1 * yi_1;
+            let xi2 = xi * xi;
+            let yi2 = yi * yi;
+
+            let dxy = xi_1 * yi - xi * yi_1;
+            let xii_1 = xi_1 + xi;
+            let yii_1 = yi_1 + yi;
+
+            a00 += dxy;
+            a10 += dxy * xii_1;
+            a01 += dxy * yii_1;
+            a20 += dxy * (xi_1 * xii_1 + xi2);
+            a11 += dxy * (xi_1 * (yii_1 + yi_1) + xi * (yii_1 + yi));
+            a02 += dxy * (yi_1 * yii_1 + yi2);
+            a30 += dxy * xii_1 * (xi_12 + xi2);
+            a03 += dxy * yii_1 * (yi_12 + yi2);
+            a21 += dxy * (xi_12 * (3.0 * yi_1 + yi) + 2.0 * xi * xi_1 * yii_1 + xi2 * (yi_1 + 3.0 * yi));
+            a12 += dxy * (yi_12 * (3.0 * xi_1 + xi) + 2.0 * yi * yi_1 * xii_1 + yi2 * (xi_1 + 3.0 * xi));
+
+            xi_1 = xi;
+            yi_1 = yi;
+        }
+
+        let db1_2 = 0.5_f64;
+        let db1_6 = 1.0 / 6.0;
+        let db1_12 = 1.0 / 12.0;
+        let db1_20 = 1.0 / 20.0;
+        let db1_24 = 1.0 / 24.0;
+        let db1_60 = 1.0 / 60.0;
+
+        let sign = if a00 < 0.0 { -1.0 } else { 1.0 };
+
+        let m00 = a00 * db1_2 * sign;
+        let m10 = a10 * db1_6 * sign;
+        let m01 = a01 * db1_6 * sign;
+        let m20 = a20 * db1_12 * sign;
+        let m11 = a11 * db1_24 * sign;
+        let m02 = a02 * db1_12 * sign;
+        let m30 = a30 * db1_20 * sign;
+        let m21 = a21 * db1_60 * sign;
+        let m12 = a12 * db1_60 * sign;
+        let m03 = a03 * db1_20 * sign;
+
+        let mut moments = Self {
+            m00,
+            m10,
+            m01,
+            m20,
+            m11,
+            m02,
+            m30,
+            m21,
+            m12,
+            m03,
+            ..Self::default()
+        };
+        moments.fill_central_and_normalized();
+        moments
+    }
+
+    fn fill_central_and_normalized(&mut self) {
+        if self.m00.abs() < 1e-12 {
+            return;
+        }
+
+        let cx = self.m10 / self.m00;
+        let cy = self.m01 / self.m00;
+
+        self.mu20 = self.m20 - cx * self.m10;
+        self.mu11 = self.m11 - cx * self.m01;
+        self.mu02 = self.m02 - cy * self.m01;
+        self.mu30 = self.m30 - 3.0 * cx * self.m20 + 2.0 * cx * cx * self.m10;
+        self.mu21 = self.m21 - 2.0 * cx * self.m11 - cy * self.m20 + 2.0 * cx * cx * self.m01;
+        self.mu12 = self.m12 - 2.0 * cy * self.m11 - cx * self.m02 + 2.0 * cy * cy * self.m10;
+        self.mu03 = self.m03 - 3.0 * cy * self.m02 + 2.0 * cy * cy * self.m01;
+
+        let m00_2 = self.m00 * self.m00;
+        let m00_25 = m00_2 * self.m00.sqrt();
+        self.nu20 = self.mu20 / m00_2;
+        self.nu11 = self.mu11 / m00_2;
+        self.nu02 = self.mu02 / m00_2;
+        self.nu30 = self.mu30 / m00_25;
+        self.nu21 = self.mu21 / m00_25;
+        self.nu12 = self.mu12 / m00_25;
+        self.nu03 = self.mu03 / m00_25;
+    }
+
+    /// Centroid `(x, y)` of the contour, i.e. `(m10/m00, m01/m00)`. Returns
+    /// `(0.0, 0.0)` for a degenerate (zero-area) contour rather than
+    /// dividing by zero.
+    pub fn centroid(&self) -> (f64, f64) {
+        if self.m00.abs() < 1e-12 {
+            (0.0, 0.0)
+        } else {
+            (self.m10 / self.m00, self.m01 / self.m00)
+        }
+    }
+
+    /// The seven Hu invariant moments, invariant to translation, scale and
+    /// rotation (and, up to a sign flip on `hu[6]`, reflection) — the same
+    /// values OpenCV's `cv::HuMoments` returns, useful as a compact shape
+    /// descriptor for matching or classifying detected regions.
+    pub fn hu(&self) -> [f64; 7] {
+        let (n20, n11, n02) = (self.nu20, self.nu11, self.nu02);
+        let (n30, n21, n12, n03) = (self.nu30, self.nu21, self.nu12, self.nu03);
+
+        let t0 = n30 + n12;
+        let t1 = n21 + n03;
+        let q0 = t0 * t0;
+        let q1 = t1 * t1;
+
+        let n4 = 4.0 * n11;
+        let s = n20 + n02;
+        let d = n20 - n02;
+
+        let hu0 = s;
+        let hu1 = d * d + n4 * n11;
+        let hu2 = (n30 - 3.0 * n12).powi(2) + (3.0 * n21 - n03).powi(2);
+        let hu3 = q0 + q1;
+        let hu5 = d * (q0 - q1) + n4 * t0 * t1;
+
+        let t2 = n30 - 3.0 * n12;
+        let t3 = 3.0 * n21 - n03;
+        let hu4 = t2 * t0 * (q0 - 3.0 * q1) + t3 * t1 * (3.0 * q0 - q1);
+        let hu6 = t3 * t0 * (q0 - 3.0 * q1) - t2 * t1 * (3.0 * q0 - q1);
+
+        [hu0, hu1, hu2, hu3, hu4, hu5, hu6]
+    }
+}
+
+/// Which borders `find_contours_with_mode` returns, matching OpenCV's
+/// retrieval modes of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetrievalMode {
+    /// Only the outer border of each connected component; holes are
+    /// traced internally (needed to keep the hierarchy correct) but not
+    /// returned. `ContourHierarchy::parent` is `None` for top-level
+    /// components; a component lying inside another's hole gets that
+    /// component's outer border as parent.
+    External,
+    /// Every border, outer and hole, in raster-scan discovery order, with
+    /// `ContourHierarchy::parent` pointing at the nearest enclosing border:
+    /// a hole's parent is the outer border around it, and an outer border
+    /// lying inside a hole has that hole as parent.
+    List,
+}
+
+/// Chain approximation method. Only `None` (every traced boundary pixel,
+/// matching OpenCV's `CHAIN_APPROX_NONE`) is implemented; `approx_poly_dp`
+/// is the intended way to reduce point count afterwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainApproxMethod {
+    None,
+}
+
+/// Hierarchy entry for a contour returned by `find_contours_with_mode`,
+/// indices matching position in that call's `Vec<Contour>`. `parent` is the
+/// nearest enclosing *returned* border (so under `RetrievalMode::External`,
+/// where holes are dropped, it skips past any filtered-out hole border to
+/// the next outer border up); `first_child`/`next_sibling` let callers walk
+/// the tree without a separate pass, matching the four-field layout of
+/// OpenCV's `findContours` hierarchy (minus `previous`, which nothing here
+/// needs since callers can get it by reversing `next_sibling`).
+#[derive(Debug, Clone, Copy)]
+pub struct ContourHierarchy {
+    pub parent: Option<usize>,
+    pub first_child: Option<usize>,
+    pub next_sibling: Option<usize>,
+    pub is_hole: bool,
+}
+
+/// 8-connected neighbor offsets in clockwise order, starting East:
+/// `trace_border` walks it forwards to find the first neighbor and
+/// backwards while following the border.
+const MOORE_DIRS: [(i32, i32); 8] = [
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+];
+
+fn moore_dir_index(from: (i32, i32), to: (i32, i32)) -> usize {
+    let delta = (to.0 - from.0, to.1 - from.1);
+    MOORE_DIRS
+        .iter()
+        .position(|&d| d == delta)
+        .expect("moore_dir_index called with non-adjacent pixels")
+}
+
+/// Find contours in a binary image using Suzuki & Abe's border-following
+/// algorithm ("Topological Structural Analysis of Digitized Binary Images
+/// by Border Following", 1985), matching OpenCV's `findContours`.
+///
+/// Foreground pixels are those with value > 127. The image is scanned in
+/// raster order; each 0-to-nonzero transition that starts a new border
+/// (outer when the left neighbor is background, hole when the right
+/// neighbor is background) is traced via Moore-neighborhood following and
+/// assigned a signed border id, with a parent id threaded through so holes
+/// nest under their enclosing outer border. Pixels outside the image are
+/// always treated as background, so contours touching the image edge are
+/// handled the same as interior ones, including closed 1-pixel-wide loops
+/// and single isolated pixels (returned as a one-point contour).
+pub fn find_contours_with_mode(
+    binary_img: &GrayImage,
+    mode: RetrievalMode,
+) -> (Vec<Contour>, Vec<ContourHierarchy>) {
     let (width, height) = binary_img.dimensions();
-    let mut label_map = vec![vec![0u32; width as usize]; height as usize];
-    let mut contours = Vec::new();
-    let mut label = 1u32;
-    
-    // First pass: label connected components using flood fill
+    let (width, height) = (width as i32, height as i32);
+
+    // f[y*width+x]: 0 = background, 1 = unvisited foreground, otherwise the
+    // signed border id (NBD) assigned when it was traced.
+    let mut f: Vec<i32> = (0..(width * height))
+        .map(|idx| {
+            let x = (idx % width) as u32;
+            let y = (idx / width) as u32;
+            if binary_img.get_pixel(x, y)[0] > 127 {
+                1
+            } else {
+                0
+            }
+        })
+        .collect();
+
+    let get = |f: &[i32], x: i32, y: i32| -> i32 {
+        if x < 0 || y < 0 || x >= width || y >= height {
+            0
+        } else {
+            f[(y * width + x) as usize]
+        }
+    };
+
+    // Indexed by border id (NBD). Id 1 is the virtual frame/background
+    // border; real traced borders start at id 2.
+    let mut parent_of: Vec<i32> = vec![0, 1];
+    let mut is_hole_of: Vec<bool> = vec![false, false];
+    let mut points_of: Vec<Vec<(i32, i32)>> = vec![Vec::new(), Vec::new()];
+
+    let mut nbd: i32 = 1;
+
     for y in 0..height {
+        let mut lnbd: i32 = 1;
+
         for x in 0..width {
-            let ux = x as usize;
-            let uy = y as usize;
-            
-            if binary_img.get_pixel(x, y)[0] > 127 && label_map[uy][ux] == 0 {
-                // Label this component
-                flood_fill_label(binary_img, &mut label_map, x as i32, y as i32, label, width as i32, height as i32);
-                label += 1;
+            let fij = get(&f, x, y);
+            if fij == 0 {
+                continue;
             }
-        }
-    }
-    
-    // Second pass: extract contours for each label
-    for current_label in 1..label {
-        // Find the topmost-leftmost pixel of this label
-        let mut _start_x = 0;
-        let mut _start_y = 0;
-        let mut found = false;
-        
-        'outer: for y in 0..height {
-            for x in 0..width {
-                if label_map[y as usize][x as usize] == current_label {
-                    _start_x = x as i32;
-                    _start_y = y as i32;
-                    found = true;
-                    break 'outer;
+
+            let is_outer_start = fij == 1 && get(&f, x - 1, y) == 0;
+            let is_hole_start = !is_outer_start && fij >= 1 && get(&f, x + 1, y) == 0;
+
+            if !is_outer_start && !is_hole_start {
+                if fij != 1 {
+                    lnbd = fij.abs();
                 }
+                continue;
+            }
+
+            nbd += 1;
+            let parent = if is_outer_start {
+                if is_hole_of[lnbd as usize] {
+                    lnbd
+                } else {
+                    parent_of[lnbd as usize]
+                }
+            } else if is_hole_of[lnbd as usize] {
+                parent_of[lnbd as usize]
+            } else {
+                lnbd
+            };
+            parent_of.push(parent);
+            is_hole_of.push(is_hole_start);
+
+            let p0 = (x, y);
+            let p2_seed = if is_outer_start { (x - 1, y) } else { (x + 1, y) };
+            points_of.push(trace_border(&mut f, width, height, p0, p2_seed, nbd));
+
+            if fij != 1 {
+                lnbd = fij.abs();
             }
         }
-        
-        if !found {
+    }
+
+    let mut contours = Vec::new();
+    let mut hierarchy = Vec::new();
+    let mut output_index_of: std::collections::HashMap<i32, usize> = std::collections::HashMap::new();
+
+    for border_id in 2..points_of.len() as i32 {
+        if mode == RetrievalMode::External && is_hole_of[border_id as usize] {
             continue;
         }
-        
-        // Note: In a future implementation, we could use start_x/start_y for contour tracing
-        // For now, we extract all boundary pixels directly
-        
-        // Extract all boundary pixels for this contour
-        let boundary_pixels = extract_boundary(&label_map, current_label, width as i32, height as i32);
-        
-        if boundary_pixels.len() >= 3 {
-            contours.push(Contour { points: boundary_pixels });
+        output_index_of.insert(border_id, contours.len());
+        contours.push(Contour {
+            points: points_of[border_id as usize].clone(),
+        });
+        hierarchy.push(ContourHierarchy {
+            parent: None,
+            first_child: None,
+            next_sibling: None,
+            is_hole: is_hole_of[border_id as usize],
+        });
+    }
+
+    // Resolve each returned border's nearest returned ancestor, walking past
+    // any borders this mode filtered out (holes, under `External`).
+    for border_id in 2..points_of.len() as i32 {
+        let out_idx = match output_index_of.get(&border_id) {
+            Some(&idx) => idx,
+            None => continue,
+        };
+
+        let mut ancestor = parent_of[border_id as usize];
+        while ancestor > 1 && !output_index_of.contains_key(&ancestor) {
+            ancestor = parent_of[ancestor as usize];
         }
+        hierarchy[out_idx].parent = output_index_of.get(&ancestor).copied();
     }
-    
-    contours
+
+    // Thread `first_child`/`next_sibling` in discovery order: the first
+    // child found under a parent becomes its `first_child`, and each later
+    // sibling is appended after the previous one found.
+    let mut last_child_of: std::collections::HashMap<usize, usize> = std::collections::HashMap::new();
+    for idx in 0..hierarchy.len() {
+        let Some(parent) = hierarchy[idx].parent else {
+            continue;
+        };
+        match last_child_of.insert(parent, idx) {
+            Some(prev_child) => hierarchy[prev_child].next_sibling = Some(idx),
+            None => hierarchy[parent].first_child = Some(idx),
+        }
+    }
+
+    (contours, hierarchy)
+}
+
+/// Trace one border starting at `p0`, whose neighbor `p2_seed` is known
+/// background (the side the 0-to-nonzero transition was detected from).
+/// Implements steps (3.1)-(3.5) of Suzuki & Abe's algorithm: find the first
+/// foreground neighbor `p1` clockwise from `p2_seed`, then walk the Moore
+/// neighborhood counterclockwise from the direction of the previous boundary
+/// pixel to find the next one, marking each visited pixel `-nbd` if its east
+/// neighbor was examined in that walk and is background (meaning the run of
+/// foreground ends there) or `nbd` if it is still unvisited, until the trace
+/// returns to `p0` having arrived from `p1` (the first neighbor found) --
+/// Jacob's stopping criterion, needed so 1-pixel-wide loops close correctly
+/// instead of stopping prematurely.
+fn trace_border(
+    f: &mut [i32],
+    width: i32,
+    height: i32,
+    p0: (i32, i32),
+    p2_seed: (i32, i32),
+    nbd: i32,
+) -> Vec<(i32, i32)> {
+    let get = |f: &[i32], x: i32, y: i32| -> i32 {
+        if x < 0 || y < 0 || x >= width || y >= height {
+            0
+        } else {
+            f[(y * width + x) as usize]
+        }
+    };
+    let set = |f: &mut [i32], x: i32, y: i32, v: i32| {
+        f[(y * width + x) as usize] = v;
+    };
+
+    let start_idx = moore_dir_index(p0, p2_seed);
+    let mut p1 = None;
+    for k in 1..=8 {
+        let idx = (start_idx + k) % 8;
+        let (dx, dy) = MOORE_DIRS[idx];
+        let cand = (p0.0 + dx, p0.1 + dy);
+        if get(f, cand.0, cand.1) != 0 {
+            p1 = Some(cand);
+            break;
+        }
+    }
+
+    let p1 = match p1 {
+        Some(p) => p,
+        None => {
+            // Isolated single pixel: no foreground neighbor at all.
+            set(f, p0.0, p0.1, -nbd);
+            return vec![p0];
+        }
+    };
+
+    let mut contour = vec![p0];
+    let mut p2 = p1;
+    let mut p3 = p0;
+
+    loop {
+        let start_idx = moore_dir_index(p3, p2);
+        let mut p4 = None;
+        let mut east_examined_is_background = false;
+
+        for k in 1..=8 {
+            let idx = (start_idx + 8 - k) % 8;
+            let (dx, dy) = MOORE_DIRS[idx];
+            let cand = (p3.0 + dx, p3.1 + dy);
+            if get(f, cand.0, cand.1) != 0 {
+                p4 = Some(cand);
+                break;
+            }
+            if idx == 0 {
+                east_examined_is_background = true;
+            }
+        }
+
+        let p4 = match p4 {
+            Some(p) => p,
+            None => {
+                set(f, p3.0, p3.1, -nbd);
+                break;
+            }
+        };
+
+        if east_examined_is_background {
+            set(f, p3.0, p3.1, -nbd);
+        } else if get(f, p3.0, p3.1) == 1 {
+            set(f, p3.0, p3.1, nbd);
+        }
+
+        if p4 == p0 && p3 == p1 {
+            break;
+        }
+
+        p2 = p3;
+        p3 = p4;
+        contour.push(p3);
+    }
+
+    contour
+}
+
+/// Find contours in a binary image.
+/// This matches OpenCV's findContours with RETR_LIST mode.
+pub fn find_contours(binary_img: &GrayImage) -> Vec<Contour> {
+    find_contours_with_mode(binary_img, RetrievalMode::List).0
 }
 
 /// Flood fill to label a connected component
@@ -605,10 +1082,42 @@ pub fn approx_simple(contour: &Contour) -> Contour {
     if let Some(&last) = contour.points.last() {
         result.points.push(last);
     }
-    
+
     result
 }
 
+/// Reduce `contour` to its dominant vertices via Douglas-Peucker polyline
+/// simplification, matching OpenCV's `approxPolyDP`. `epsilon` is the
+/// maximum perpendicular distance (in pixels) a discarded point may have
+/// strayed from the simplified boundary; sweeping it up lets detection
+/// collapse a noisy traced border down to a clean quad. `closed` treats
+/// `contour` as a closed polygon (as every `find_contours` output is): the
+/// algorithm first splits it at its two most distant vertices and
+/// simplifies each resulting arc independently, so the result doesn't
+/// depend on which point the border tracer happened to start from. The
+/// recursion itself lives in `crate::rdp`, shared with the det pipeline's
+/// own `approx_poly_dp`s over `Point2f` contours.
+pub fn approx_poly_dp(contour: &Contour, epsilon: f64, closed: bool) -> Contour {
+    let points = &contour.points;
+    if points.len() < 2 {
+        return contour.clone();
+    }
+
+    let as_f64: Vec<(f64, f64)> = points.iter().map(|&(x, y)| (x as f64, y as f64)).collect();
+    let simplified = if points.len() < 3 || !closed {
+        crate::rdp::simplify_open(&as_f64, epsilon)
+    } else {
+        crate::rdp::simplify_closed(&as_f64, epsilon)
+    };
+
+    Contour {
+        points: simplified
+            .into_iter()
+            .map(|(x, y)| (x.round() as i32, y.round() as i32))
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -628,4 +1137,187 @@ mod tests {
         let contours = find_contours(&img);
         assert!(!contours.is_empty(), "Should find at least one contour");
     }
+
+    #[test]
+    fn test_moments_square_centroid_and_area() {
+        // A 10x10 axis-aligned square traced clockwise from the top-left.
+        let square = Contour {
+            points: vec![(0, 0), (10, 0), (10, 10), (0, 10)],
+        };
+
+        let m = square.moments();
+        assert!((m.m00 - 100.0).abs() < 1e-6);
+        let (cx, cy) = m.centroid();
+        assert!((cx - 5.0).abs() < 1e-6);
+        assert!((cy - 5.0).abs() < 1e-6);
+
+        // A square is symmetric enough that the off-axis central moments
+        // vanish and Hu's first invariant reduces to a known constant.
+        assert!(m.mu11.abs() < 1e-6);
+        assert!((m.hu()[0] - 1.0 / 6.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_hu_moments_are_scale_invariant_for_asymmetric_contour() {
+        // A right triangle is asymmetric enough to give nonzero nu30/nu21/
+        // nu12/nu03 (a square's symmetry makes those vanish and wouldn't
+        // exercise the `m00_25` divisor at all). Scaling every vertex by a
+        // constant factor must leave all seven Hu invariants unchanged.
+        let triangle = Contour {
+            points: vec![(0, 0), (8, 0), (0, 6)],
+        };
+        let scaled = Contour {
+            points: vec![(0, 0), (24, 0), (0, 18)],
+        };
+
+        let hu_small = triangle.moments().hu();
+        let hu_large = scaled.moments().hu();
+
+        for i in 0..7 {
+            assert!(
+                (hu_small[i] - hu_large[i]).abs() < 1e-6,
+                "hu[{i}] not scale-invariant: {} vs {}",
+                hu_small[i],
+                hu_large[i]
+            );
+        }
+        // Sanity check that the asymmetric third-order moments are actually
+        // nonzero, so this test would have caught the `m00_25` regression.
+        assert!(triangle.moments().nu30.abs() > 1e-9);
+    }
+
+    #[test]
+    fn test_find_contours_with_mode_square_is_ordered() {
+        let mut img = GrayImage::new(10, 10);
+        for x in 2..8 {
+            for y in 2..8 {
+                img.put_pixel(x, y, Luma([255]));
+            }
+        }
+
+        let (contours, hierarchy) = find_contours_with_mode(&img, RetrievalMode::External);
+        assert_eq!(contours.len(), 1);
+        assert_eq!(hierarchy.len(), 1);
+        assert!(!hierarchy[0].is_hole);
+        assert!(hierarchy[0].parent.is_none());
+
+        // Every consecutive pair of traced points should be 8-connected, and
+        // the contour should cover the full perimeter of a 6x6 square.
+        let points = &contours[0].points;
+        assert!(points.len() >= 20);
+        for w in points.windows(2) {
+            let (dx, dy) = (w[1].0 - w[0].0, w[1].1 - w[0].1);
+            assert!(dx.abs() <= 1 && dy.abs() <= 1 && (dx, dy) != (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_find_contours_with_mode_hole_hierarchy() {
+        let mut img = GrayImage::new(12, 12);
+        for x in 1..11 {
+            for y in 1..11 {
+                img.put_pixel(x, y, Luma([255]));
+            }
+        }
+        // Punch a hole in the middle of the filled square.
+        for x in 4..7 {
+            for y in 4..7 {
+                img.put_pixel(x, y, Luma([0]));
+            }
+        }
+
+        let (contours, hierarchy) = find_contours_with_mode(&img, RetrievalMode::List);
+        assert_eq!(contours.len(), 2);
+
+        let outer_idx = hierarchy.iter().position(|h| !h.is_hole).unwrap();
+        let hole_idx = hierarchy.iter().position(|h| h.is_hole).unwrap();
+        assert_eq!(hierarchy[hole_idx].parent, Some(outer_idx));
+        assert!(hierarchy[outer_idx].parent.is_none());
+        assert_eq!(hierarchy[outer_idx].first_child, Some(hole_idx));
+        assert!(hierarchy[hole_idx].next_sibling.is_none());
+
+        // RETR_EXTERNAL must drop the hole border entirely.
+        let (ext_contours, ext_hierarchy) = find_contours_with_mode(&img, RetrievalMode::External);
+        assert_eq!(ext_contours.len(), 1);
+        assert!(!ext_hierarchy[0].is_hole);
+    }
+
+    #[test]
+    fn test_find_contours_with_mode_single_pixel() {
+        let mut img = GrayImage::new(5, 5);
+        img.put_pixel(2, 2, Luma([255]));
+
+        let (contours, hierarchy) = find_contours_with_mode(&img, RetrievalMode::List);
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].points, vec![(2, 2)]);
+        assert!(!hierarchy[0].is_hole);
+    }
+
+    #[test]
+    fn test_find_contours_with_mode_touches_boundary() {
+        // A filled square touching the top-left corner of the image.
+        let mut img = GrayImage::new(6, 6);
+        for x in 0..3 {
+            for y in 0..3 {
+                img.put_pixel(x, y, Luma([255]));
+            }
+        }
+
+        let (contours, hierarchy) = find_contours_with_mode(&img, RetrievalMode::External);
+        assert_eq!(contours.len(), 1);
+        assert!(!hierarchy[0].is_hole);
+        assert!(contours[0].points.len() >= 8);
+    }
+
+    #[test]
+    fn test_approx_poly_dp_noisy_square_collapses_to_quad() {
+        let mut img = GrayImage::new(20, 20);
+        for x in 2..16 {
+            for y in 2..16 {
+                img.put_pixel(x, y, Luma([255]));
+            }
+        }
+
+        let (contours, _) = find_contours_with_mode(&img, RetrievalMode::External);
+        let simplified = approx_poly_dp(&contours[0], 1.5, true);
+        assert_eq!(simplified.points.len(), 4);
+    }
+
+    #[test]
+    fn test_approx_poly_dp_open_line_keeps_endpoints() {
+        let line = Contour {
+            points: vec![(0, 0), (1, 0), (2, 0), (3, 1), (4, 0), (5, 0)],
+        };
+        let simplified = approx_poly_dp(&line, 0.1, false);
+        assert_eq!(simplified.points.first(), Some(&(0, 0)));
+        assert_eq!(simplified.points.last(), Some(&(5, 0)));
+        // The (3, 1) spike should survive a tight epsilon.
+        assert!(simplified.points.contains(&(3, 1)));
+    }
+
+    #[test]
+    fn test_signed_area_sign_matches_winding_order() {
+        // Clockwise in image coordinates (y grows downward): negative area.
+        let clockwise = Contour {
+            points: vec![(0, 0), (10, 0), (10, 10), (0, 10)],
+        };
+        assert!(clockwise.signed_area() < 0.0);
+
+        // Same square, reversed winding: positive area, same magnitude.
+        let counter_clockwise = Contour {
+            points: vec![(0, 0), (0, 10), (10, 10), (10, 0)],
+        };
+        assert!(counter_clockwise.signed_area() > 0.0);
+        assert!((clockwise.signed_area() + counter_clockwise.signed_area()).abs() < 1e-6);
+        assert!((clockwise.signed_area().abs() - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_signed_area_degenerate_contour_is_zero() {
+        // A 2-point "contour" has no area, and shouldn't panic on the `n < 3` guard.
+        let degenerate = Contour {
+            points: vec![(0, 0), (5, 5)],
+        };
+        assert_eq!(degenerate.signed_area(), 0.0);
+    }
 }
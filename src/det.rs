@@ -1,8 +1,12 @@
 use std::time::Instant;
 
-use ndarray::{Array4, ArrayD};
+use ndarray::{Array4, ArrayD, Axis};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use crate::engine::{EngineError, OrtSession};
+use crate::geometry::Quad;
 use crate::postprocess::{DBPostProcess, TextDetOutput};
 use crate::preprocess::DetPreProcess;
 use crate::types::DetConfig;
@@ -28,7 +32,10 @@ impl TextDetector {
             cfg.max_candidates,
             cfg.unclip_ratio,
             cfg.use_dilation,
-        );
+        )
+        .with_score_mode(cfg.score_mode.clone())
+        .with_box_type(cfg.box_type.clone())
+        .with_sub_pixel_contours(cfg.sub_pixel_contours, cfg.contour_precision);
         Ok(Self {
             cfg,
             session,
@@ -39,6 +46,137 @@ impl TextDetector {
     pub fn run(&mut self, img: &Mat) -> Result<TextDetOutput, EngineError> {
         let start = Instant::now();
 
+        let (input, ori_h, ori_w) = self.preprocess_one(img)?;
+        let preds_dyn = self.session.run(input.into_dyn())?;
+        let preds: Array4<f32> = preds_dyn
+            .into_dimensionality()
+            .map_err(|_| EngineError::InvalidInputShape)?;
+        let (mut boxes, scores) = self.postprocess.process(&preds, ori_h, ori_w)?;
+        if boxes.is_empty() {
+            return Ok(TextDetOutput::empty());
+        }
+
+        self.sorted_boxes(&mut boxes);
+        let polys = if self.cfg.box_type == "poly" {
+            Some(self.postprocess.process_poly(&preds, ori_h, ori_w)?.0)
+        } else {
+            None
+        };
+        let elapse = start.elapsed().as_secs_f64();
+
+        Ok(TextDetOutput {
+            img: None,
+            boxes: Some(boxes.into_iter().map(Quad::new).collect()),
+            scores: Some(scores),
+            polys,
+            elapse,
+        })
+    }
+
+    /// Run detection across many images in one call. Images whose preprocessed
+    /// tensors share the same `(channels, height, width)` are stacked into a
+    /// single batched session invocation instead of one run per image, so the
+    /// thread pool and the model's own batching aren't wasted on singletons.
+    ///
+    /// Preprocessing and per-image box post-processing run across cores when
+    /// built with the `parallel` feature; otherwise they run serially in
+    /// image order.
+    pub fn run_batch(&mut self, imgs: &[Mat]) -> Result<Vec<TextDetOutput>, EngineError> {
+        if imgs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        #[cfg(feature = "parallel")]
+        let prepped: Vec<Result<(Array4<f32>, i32, i32), EngineError>> =
+            imgs.par_iter().map(|img| self.preprocess_one(img)).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let prepped: Vec<Result<(Array4<f32>, i32, i32), EngineError>> =
+            imgs.iter().map(|img| self.preprocess_one(img)).collect();
+
+        // Group images by resulting tensor shape so same-size inputs share a
+        // single batched session call.
+        let mut groups: std::collections::BTreeMap<(usize, usize, usize), Vec<usize>> =
+            std::collections::BTreeMap::new();
+        let mut tensors: Vec<Option<Array4<f32>>> = Vec::with_capacity(imgs.len());
+        let mut ori_sizes: Vec<(i32, i32)> = Vec::with_capacity(imgs.len());
+
+        for item in prepped {
+            let (tensor, ori_h, ori_w) = item?;
+            let (_, c, h, w) = tensor.dim();
+            let idx = tensors.len();
+            groups.entry((c, h, w)).or_default().push(idx);
+            tensors.push(Some(tensor));
+            ori_sizes.push((ori_h, ori_w));
+        }
+
+        let mut outputs: Vec<Option<TextDetOutput>> = (0..imgs.len()).map(|_| None).collect();
+
+        for (_, indices) in groups {
+            let n = indices.len();
+            let (_, c, h, w) = tensors[indices[0]].as_ref().unwrap().dim();
+
+            let mut batch = Array4::<f32>::zeros((n, c, h, w));
+            for (slot, &idx) in indices.iter().enumerate() {
+                let tensor = tensors[idx].take().unwrap();
+                batch
+                    .slice_mut(ndarray::s![slot, .., .., ..])
+                    .assign(&tensor.index_axis(Axis(0), 0));
+            }
+
+            let preds_dyn = self.session.run(batch.into_dyn())?;
+            let preds: Array4<f32> = preds_dyn
+                .into_dimensionality()
+                .map_err(|_| EngineError::InvalidInputShape)?;
+
+            let postprocess_slot = |slot: usize, idx: usize| -> Result<TextDetOutput, EngineError> {
+                let single = preds
+                    .index_axis(Axis(0), slot)
+                    .insert_axis(Axis(0))
+                    .to_owned();
+                let (ori_h, ori_w) = ori_sizes[idx];
+                let (mut boxes, scores) = self.postprocess.process(&single, ori_h, ori_w)?;
+                if boxes.is_empty() {
+                    return Ok(TextDetOutput::empty());
+                }
+                self.sorted_boxes(&mut boxes);
+                let polys = if self.cfg.box_type == "poly" {
+                    Some(self.postprocess.process_poly(&single, ori_h, ori_w)?.0)
+                } else {
+                    None
+                };
+                Ok(TextDetOutput {
+                    img: None,
+                    boxes: Some(boxes.into_iter().map(Quad::new).collect()),
+                    scores: Some(scores),
+                    polys,
+                    elapse: 0.0,
+                })
+            };
+
+            #[cfg(feature = "parallel")]
+            let per_image: Vec<Result<TextDetOutput, EngineError>> = indices
+                .par_iter()
+                .enumerate()
+                .map(|(slot, &idx)| postprocess_slot(slot, idx))
+                .collect();
+
+            #[cfg(not(feature = "parallel"))]
+            let per_image: Vec<Result<TextDetOutput, EngineError>> = indices
+                .iter()
+                .enumerate()
+                .map(|(slot, &idx)| postprocess_slot(slot, idx))
+                .collect();
+
+            for (&idx, result) in indices.iter().zip(per_image.into_iter()) {
+                outputs[idx] = Some(result?);
+            }
+        }
+
+        Ok(outputs.into_iter().map(|o| o.unwrap()).collect())
+    }
+
+    fn preprocess_one(&self, img: &Mat) -> Result<(Array4<f32>, i32, i32), EngineError> {
         let ori_h = img.rows();
         let ori_w = img.cols();
         let max_wh = ori_h.max(ori_w);
@@ -58,27 +196,12 @@ impl TextDetector {
             self.cfg.limit_type.clone(),
             self.cfg.mean,
             self.cfg.std,
+            self.cfg.input_color,
+            self.cfg.background,
+            self.cfg.preprocess_threads,
         );
         let input = pre.run(img)?;
-        let input_dyn: ArrayD<f32> = input.into_dyn();
-        let preds_dyn = self.session.run(input_dyn)?;
-        let preds: Array4<f32> = preds_dyn
-            .into_dimensionality()
-            .map_err(|_| EngineError::InvalidInputShape)?;
-        let (mut boxes, scores) = self.postprocess.process(&preds, ori_h, ori_w)?;
-        if boxes.is_empty() {
-            return Ok(TextDetOutput::empty());
-        }
-
-        self.sorted_boxes(&mut boxes);
-        let elapse = start.elapsed().as_secs_f64();
-
-        Ok(TextDetOutput {
-            img: None,
-            boxes: Some(boxes),
-            scores: Some(scores),
-            elapse,
-        })
+        Ok((input, ori_h, ori_w))
     }
 
     #[cfg(feature = "use-opencv")]
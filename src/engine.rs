@@ -1,9 +1,11 @@
 use std::path::Path;
 
 use ndarray::{ArrayD, Array};
-use mnn::{BackendConfig, ForwardType, Interpreter, PrecisionMode, ScheduleConfig, PowerMode};
+use mnn::{BackendConfig, ForwardType, Interpreter, ScheduleConfig};
 
-use crate::types::{DetConfig, EngineConfig, RecConfig};
+use crate::types::{
+    ClsConfig, DetConfig, EngineConfig, ExecutionProvider, PowerMode, PrecisionMode, RecConfig,
+};
 
 #[derive(thiserror::Error, Debug)]
 pub enum EngineError {
@@ -36,12 +38,24 @@ impl From<Box<dyn std::error::Error>> for EngineError {
     }
 }
 
+/// Transparently inflate a `.gz`/`.zlib`-compressed model file (detected by
+/// extension or magic header) into memory. Returns `None` when the file
+/// isn't compressed, so the caller falls back to `Interpreter::from_file`
+/// instead of paying for an extra read of a potentially large model.
+fn load_model_bytes(path: &Path) -> Result<Option<Vec<u8>>, EngineError> {
+    crate::inflate::maybe_inflate_file(path)
+}
+
 pub struct MnnSession {
     interpreter: Interpreter,
     session: Option<mnn::Session>,
     input_tensor_name: Option<String>,
     output_tensor_name: Option<String>,
     last_input_shape: Option<[i32; 4]>,
+    execution_providers: Vec<ExecutionProvider>,
+    num_threads: i32,
+    precision_mode: PrecisionMode,
+    power_mode: PowerMode,
 }
 
 impl Drop for MnnSession {
@@ -64,31 +78,92 @@ impl MnnSession {
         Self::from_path(&cfg.model_path, &cfg.engine_cfg)
     }
 
-    fn from_path(model_path: &Path, _engine_cfg: &EngineConfig) -> Result<Self, EngineError> {        
-        let interpreter = Interpreter::from_file(model_path)?;
-        
-        Ok(Self { 
+    pub fn from_cls_config(cfg: &ClsConfig) -> Result<Self, EngineError> {
+        Self::from_path(&cfg.model_path, &cfg.engine_cfg)
+    }
+
+    fn from_path(model_path: &Path, engine_cfg: &EngineConfig) -> Result<Self, EngineError> {
+        let interpreter = match load_model_bytes(model_path)? {
+            Some(bytes) => Interpreter::from_bytes(&bytes)?,
+            None => Interpreter::from_file(model_path)?,
+        };
+
+        Ok(Self {
             interpreter,
             session: None,
             input_tensor_name: None,
             output_tensor_name: None,
             last_input_shape: None,
+            execution_providers: engine_cfg.execution_providers.clone(),
+            num_threads: engine_cfg.intra_op_num_threads,
+            precision_mode: engine_cfg.precision_mode,
+            power_mode: engine_cfg.power_mode,
         })
     }
 
+    /// Map a requested execution provider onto the closest MNN forward type.
+    /// MNN has no first-class notion of TensorRT/CoreML/DirectML, so those
+    /// providers are routed to the GPU backend MNN actually ships for the
+    /// host platform; `Cpu` always maps directly.
+    fn forward_type_for(provider: ExecutionProvider) -> ForwardType {
+        match provider {
+            ExecutionProvider::Cpu => ForwardType::CPU,
+            #[cfg(feature = "cuda")]
+            ExecutionProvider::Cuda { .. } => ForwardType::CUDA,
+            #[cfg(feature = "tensorrt")]
+            ExecutionProvider::TensorRt => ForwardType::CUDA,
+            #[cfg(feature = "coreml")]
+            ExecutionProvider::CoreMl => ForwardType::Metal,
+            #[cfg(feature = "directml")]
+            ExecutionProvider::DirectMl => ForwardType::OpenCL,
+        }
+    }
+
+    fn precision_mode_for(mode: PrecisionMode) -> mnn::PrecisionMode {
+        match mode {
+            PrecisionMode::Low => mnn::PrecisionMode::Low,
+            PrecisionMode::Normal => mnn::PrecisionMode::Normal,
+            PrecisionMode::High => mnn::PrecisionMode::High,
+            PrecisionMode::Lossless => mnn::PrecisionMode::Lossless,
+        }
+    }
+
+    fn power_mode_for(mode: PowerMode) -> mnn::PowerMode {
+        match mode {
+            PowerMode::Low => mnn::PowerMode::Low,
+            PowerMode::Normal => mnn::PowerMode::Normal,
+            PowerMode::High => mnn::PowerMode::High,
+        }
+    }
+
     fn ensure_session(&mut self) -> Result<(), EngineError> {
         if self.session.is_none() {
-            let mut config = ScheduleConfig::new();
-            config.set_type(ForwardType::Auto);
+            let providers = self.execution_providers.clone();
+            let mut last_err = None;
 
-            let mut backend_config = BackendConfig::new();
-            backend_config.set_precision_mode(PrecisionMode::High);
-            backend_config.set_power_mode(PowerMode::High);
+            for provider in providers.iter().chain(std::iter::once(&ExecutionProvider::Cpu)) {
+                let mut config = ScheduleConfig::new();
+                config.set_type(Self::forward_type_for(*provider));
+                config.set_num_thread(self.num_threads);
 
-            config.set_backend_config(backend_config);
+                let mut backend_config = BackendConfig::new();
+                backend_config.set_precision_mode(Self::precision_mode_for(self.precision_mode));
+                backend_config.set_power_mode(Self::power_mode_for(self.power_mode));
+                config.set_backend_config(backend_config);
 
-            let session = self.interpreter.create_session(config)?;
-            self.session = Some(session);
+                match self.interpreter.create_session(config) {
+                    Ok(session) => {
+                        self.session = Some(session);
+                        return Ok(());
+                    }
+                    Err(err) => last_err = Some(err),
+                }
+            }
+
+            // Every requested provider (including the CPU fallback) failed.
+            if let Some(err) = last_err {
+                return Err(err.into());
+            }
         }
         Ok(())
     }
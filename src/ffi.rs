@@ -137,6 +137,88 @@ pub unsafe extern "C" fn rocr_ocr_data(
     0
 }
 
+/// Run OCR on many image files in one call, sharing det/rec/cls sessions
+/// across all of them (see `RapidOCR::ocr_batch_shared`) instead of paying
+/// per-image pipeline setup `rocr_ocr_file` would incur once per call.
+///
+/// On success, `results_out`/`counts_out` each receive one entry per input
+/// path (`path_count` entries), in the same order; free both with
+/// `rocr_free_batch_results`.
+///
+/// # Safety
+/// - handle must be a valid pointer returned from rocr_new
+/// - image_paths must point to path_count valid null-terminated UTF-8 strings
+/// - results_out/counts_out will be allocated and must be freed with rocr_free_batch_results
+#[no_mangle]
+pub unsafe extern "C" fn rocr_ocr_file_batch(
+    handle: *mut ROCRHandle,
+    image_paths: *const *const c_char,
+    path_count: usize,
+    results_out: *mut *mut *mut CTextResult,
+    counts_out: *mut *mut usize,
+) -> c_int {
+    if handle.is_null() || image_paths.is_null() || results_out.is_null() || counts_out.is_null() {
+        return -1;
+    }
+
+    let ocr = &mut (*handle).inner;
+    let path_ptrs = slice::from_raw_parts(image_paths, path_count);
+
+    let mut paths = Vec::with_capacity(path_count);
+    for &p in path_ptrs {
+        if p.is_null() {
+            return -2;
+        }
+        match CStr::from_ptr(p).to_str() {
+            Ok(s) => paths.push(s),
+            Err(_) => return -2,
+        }
+    }
+
+    let per_image_results = match ocr.ocr_batch_shared(&paths) {
+        Ok(r) => r,
+        Err(_) => return -3,
+    };
+
+    let mut result_ptrs = Vec::with_capacity(per_image_results.len());
+    let mut counts = Vec::with_capacity(per_image_results.len());
+    for results in per_image_results {
+        let c_results = results_to_c(results);
+        counts.push(c_results.len());
+        result_ptrs.push(c_results.as_ptr() as *mut CTextResult);
+        std::mem::forget(c_results);
+    }
+
+    *counts_out = counts.as_mut_ptr();
+    std::mem::forget(counts);
+    *results_out = result_ptrs.as_mut_ptr();
+    std::mem::forget(result_ptrs);
+
+    0
+}
+
+/// Free the arrays returned by `rocr_ocr_file_batch`.
+///
+/// # Safety
+/// - results and counts must be the pointers returned from rocr_ocr_file_batch
+/// - path_count must match the path_count passed to that call
+#[no_mangle]
+pub unsafe extern "C" fn rocr_free_batch_results(
+    results: *mut *mut CTextResult,
+    counts: *mut usize,
+    path_count: usize,
+) {
+    if results.is_null() || counts.is_null() {
+        return;
+    }
+
+    let result_ptrs = Vec::from_raw_parts(results, path_count, path_count);
+    let counts_vec = Vec::from_raw_parts(counts, path_count, path_count);
+    for (ptr, count) in result_ptrs.into_iter().zip(counts_vec.into_iter()) {
+        rocr_free_results(ptr, count);
+    }
+}
+
 /// Free results returned from rocr_ocr
 ///
 /// # Safety
@@ -174,6 +256,73 @@ pub extern "C" fn rocr_version() -> *const c_char {
     VERSION.as_ptr() as *const c_char
 }
 
+/// A C callback invoked once per `log` record once installed via
+/// `rocr_set_log_callback`: `level` is `1` (error) through `5` (trace), and
+/// `msg` is a null-terminated UTF-8 string valid only for the duration of
+/// the call.
+pub type RocrLogCallback = extern "C" fn(level: c_int, msg: *const c_char);
+
+static LOG_CALLBACK: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+fn level_to_c_int(level: log::Level) -> c_int {
+    match level {
+        log::Level::Error => 1,
+        log::Level::Warn => 2,
+        log::Level::Info => 3,
+        log::Level::Debug => 4,
+        log::Level::Trace => 5,
+    }
+}
+
+/// Forwards every `log` record the pipeline emits (box accept/reject
+/// decisions, etc.) to whatever callback `rocr_set_log_callback` last
+/// installed, so C/C++/C# hosts can route them into their own logger
+/// instead of inheriting Rust's stderr.
+struct FfiLogger;
+
+impl log::Log for FfiLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        LOG_CALLBACK.load(std::sync::atomic::Ordering::Relaxed) != 0
+    }
+
+    fn log(&self, record: &log::Record) {
+        let ptr = LOG_CALLBACK.load(std::sync::atomic::Ordering::Relaxed);
+        if ptr == 0 {
+            return;
+        }
+        // SAFETY: the only value ever stored is a `RocrLogCallback` cast to
+        // `usize` by `rocr_set_log_callback`.
+        let callback: RocrLogCallback = unsafe { std::mem::transmute(ptr) };
+        if let Ok(msg) = CString::new(format!("{}", record.args())) {
+            callback(level_to_c_int(record.level()), msg.as_ptr());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static FFI_LOGGER: FfiLogger = FfiLogger;
+
+/// Install `cb` as the destination for every `log` record this library
+/// emits, replacing stderr `eprintln!`-style output with structured,
+/// host-controlled logging. Safe to call more than once to swap callbacks.
+///
+/// # Safety
+/// `cb` must be a valid function pointer for the lifetime of the process
+/// (or until replaced by another `rocr_set_log_callback` call), since it may
+/// be invoked from any thread that drives the OCR pipeline.
+#[no_mangle]
+pub unsafe extern "C" fn rocr_set_log_callback(cb: RocrLogCallback) -> c_int {
+    LOG_CALLBACK.store(cb as usize, std::sync::atomic::Ordering::Relaxed);
+    match log::set_logger(&FFI_LOGGER) {
+        Ok(()) => log::set_max_level(log::LevelFilter::Trace),
+        // Already installed by an earlier call; the callback pointer above
+        // was updated in place, so logging still reflects the new callback.
+        Err(_) => {}
+    }
+    0
+}
+
 // Helper function to convert Rust results to C results
 fn results_to_c(results: Vec<TextResult>) -> Vec<CTextResult> {
     results
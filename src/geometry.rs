@@ -16,8 +16,49 @@ use crate::image_impl::{self, Mat, Point2f, Result as ImgResult, Size, INTER_LIN
 
 pub type OpRecord = BTreeMap<String, BTreeMap<String, f32>>;
 
+/// Four corners of a detected text box, ordered top-left, top-right,
+/// bottom-right, bottom-left.
+///
+/// A thin wrapper around `[Point2f; 4]` that derefs to the array, so the
+/// existing point-array helpers (`get_rotate_crop_image`, `quads_to_rect_bbox`,
+/// etc.) keep working unchanged while detection output carries a named type
+/// instead of a bare array.
+#[derive(Clone, Copy, Debug)]
+pub struct Quad(pub [Point2f; 4]);
+
+impl Quad {
+    pub fn new(points: [Point2f; 4]) -> Self {
+        Self(points)
+    }
+}
+
+impl std::ops::Deref for Quad {
+    type Target = [Point2f; 4];
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for Quad {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<[Point2f; 4]> for Quad {
+    fn from(points: [Point2f; 4]) -> Self {
+        Self(points)
+    }
+}
+
+impl From<Quad> for [Point2f; 4] {
+    fn from(quad: Quad) -> Self {
+        quad.0
+    }
+}
+
 pub fn map_boxes_to_original(
-    dt_boxes: &mut [[Point2f; 4]],
+    dt_boxes: &mut [Quad],
     op_record: &OpRecord,
     ori_h: i32,
     ori_w: i32,
@@ -92,15 +133,55 @@ fn get_padding_h(h: i32, w: i32, width_height_ratio: f32, min_height: f32) -> i3
     ((new_h - h).abs() / 2) as i32
 }
 
+/// Re-order an unordered (but convex, non-self-intersecting) quad's four
+/// corners into top-left, top-right, bottom-right, bottom-left using the
+/// sum/difference trick: the smallest and largest `x + y` give the top-left
+/// and bottom-right corners, and the smallest and largest `x - y` give the
+/// top-right and bottom-left corners. Unlike `postprocess`'s
+/// `order_points_clockwise[_pure]` (which sorts by `x` alone and assumes a
+/// roughly axis-aligned box), this holds for any rotation, which is what
+/// `get_rotate_crop_image`'s homography needs to land the quad's actual
+/// corners on the destination rectangle's.
+pub fn order_quad_corners(points: &[Point2f; 4]) -> [Point2f; 4] {
+    let sum_idx = |best: fn(f32, f32) -> bool| {
+        let mut idx = 0;
+        for i in 1..4 {
+            if best(points[i].x + points[i].y, points[idx].x + points[idx].y) {
+                idx = i;
+            }
+        }
+        idx
+    };
+    let diff_idx = |best: fn(f32, f32) -> bool| {
+        let mut idx = 0;
+        for i in 1..4 {
+            if best(points[i].x - points[i].y, points[idx].x - points[idx].y) {
+                idx = i;
+            }
+        }
+        idx
+    };
+
+    let tl = points[sum_idx(|a, b| a < b)];
+    let br = points[sum_idx(|a, b| a > b)];
+    let tr = points[diff_idx(|a, b| a > b)];
+    let bl = points[diff_idx(|a, b| a < b)];
+
+    [tl, tr, br, bl]
+}
+
 #[cfg(feature = "use-opencv")]
 pub fn get_rotate_crop_image(img: &Mat, points: &[Point2f; 4]) -> ImgResult<Mat> {
+    let points = order_quad_corners(points);
+    let points = &points;
+
     let w1 = (points[0].x - points[1].x).hypot(points[0].y - points[1].y);
     let w2 = (points[2].x - points[3].x).hypot(points[2].y - points[3].y);
-    let img_crop_width = w1.max(w2) as i32;
+    let img_crop_width = ((w1 + w2) / 2.0) as i32;
 
     let h1 = (points[0].x - points[3].x).hypot(points[0].y - points[3].y);
     let h2 = (points[1].x - points[2].x).hypot(points[1].y - points[2].y);
-    let img_crop_height = h1.max(h2) as i32;
+    let img_crop_height = ((h1 + h2) / 2.0) as i32;
 
     let pts_src = core::Mat::from_slice_2d(&[
         [points[0].x, points[0].y],
@@ -142,13 +223,16 @@ pub fn get_rotate_crop_image(img: &Mat, points: &[Point2f; 4]) -> ImgResult<Mat>
 
 #[cfg(not(feature = "use-opencv"))]
 pub fn get_rotate_crop_image(img: &Mat, points: &[Point2f; 4]) -> ImgResult<Mat> {
+    let points = order_quad_corners(points);
+    let points = &points;
+
     let w1 = (points[0].x - points[1].x).hypot(points[0].y - points[1].y);
     let w2 = (points[2].x - points[3].x).hypot(points[2].y - points[3].y);
-    let img_crop_width = w1.max(w2) as i32;
+    let img_crop_width = ((w1 + w2) / 2.0) as i32;
 
     let h1 = (points[0].x - points[3].x).hypot(points[0].y - points[3].y);
     let h2 = (points[1].x - points[2].x).hypot(points[1].y - points[2].y);
-    let img_crop_height = h1.max(h2) as i32;
+    let img_crop_height = ((h1 + h2) / 2.0) as i32;
 
     let pts_src = [
         [points[0].x, points[0].y],
@@ -187,6 +271,60 @@ pub fn get_rotate_crop_image(img: &Mat, points: &[Point2f; 4]) -> ImgResult<Mat>
     }
 }
 
+/// Crop the axis-aligned bounding rectangle of `points` out of `img`,
+/// skipping the perspective warp `get_rotate_crop_image` does. Cheaper, but
+/// leaves a keystoned/rotated quad's slant baked into the crop; selected by
+/// `GlobalConfig::rectify_quads = false` for callers who'd rather pay that
+/// accuracy cost than the warp's.
+#[cfg(feature = "use-opencv")]
+pub fn crop_axis_aligned(img: &Mat, points: &[Point2f; 4]) -> ImgResult<Mat> {
+    let (w, h) = (img.cols() as f32, img.rows() as f32);
+    let xmin = points.iter().map(|p| p.x).fold(f32::INFINITY, f32::min).max(0.0);
+    let xmax = points.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max).min(w - 1.0);
+    let ymin = points.iter().map(|p| p.y).fold(f32::INFINITY, f32::min).max(0.0);
+    let ymax = points.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max).min(h - 1.0);
+
+    let rect = core::Rect::new(
+        xmin as i32,
+        ymin as i32,
+        (xmax - xmin).max(1.0) as i32,
+        (ymax - ymin).max(1.0) as i32,
+    );
+    let roi = core::Mat::roi(img, rect)?;
+    roi.try_clone()
+}
+
+#[cfg(not(feature = "use-opencv"))]
+pub fn crop_axis_aligned(img: &Mat, points: &[Point2f; 4]) -> ImgResult<Mat> {
+    let size = img.size()?;
+    let (w, h) = (size.width as f32, size.height as f32);
+    let xmin = points.iter().map(|p| p.x).fold(f32::INFINITY, f32::min).max(0.0);
+    let xmax = points.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max).min(w - 1.0);
+    let ymin = points.iter().map(|p| p.y).fold(f32::INFINITY, f32::min).max(0.0);
+    let ymax = points.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max).min(h - 1.0);
+
+    let rgb = img.to_rgb8();
+    let crop_w = (xmax - xmin).max(1.0).round() as u32;
+    let crop_h = (ymax - ymin).max(1.0).round() as u32;
+    let cropped = image::imageops::crop_imm(&rgb, xmin.round() as u32, ymin.round() as u32, crop_w, crop_h)
+        .to_image();
+    Ok(Mat::new(image::DynamicImage::ImageRgb8(cropped)))
+}
+
+/// Crop the region bounded by `points` out of `img`, rectifying a slanted
+/// quad via perspective warp (`get_rotate_crop_image`) when `rectify` is
+/// true, or taking a plain axis-aligned bounding-rect crop otherwise. The
+/// `RapidOcr::run`/`run_on_mat_timed`/`run_batch_mats` crop loops call this
+/// instead of `get_rotate_crop_image` directly so `GlobalConfig::rectify_quads`
+/// can toggle the behavior.
+pub fn crop_quad(img: &Mat, points: &[Point2f; 4], rectify: bool) -> ImgResult<Mat> {
+    if rectify {
+        get_rotate_crop_image(img, points)
+    } else {
+        crop_axis_aligned(img, points)
+    }
+}
+
 pub fn resize_image_within_bounds(
     img: &Mat,
     min_side_len: f32,
@@ -363,15 +501,15 @@ pub fn add_round_letterbox(
     padding: (i32, i32, i32, i32),
 ) -> Result<Mat, EngineError> {
     use image::{RgbImage, Rgb};
-    
+
     let rgb_img = img.to_rgb8();
     let (width, height) = rgb_img.dimensions();
-    
+
     let new_width = width + padding.2 as u32 + padding.3 as u32;
     let new_height = height + padding.0 as u32 + padding.1 as u32;
-    
+
     let mut new_img = RgbImage::from_pixel(new_width, new_height, Rgb([0, 0, 0]));
-    
+
     // Copy original image to center
     for y in 0..height {
         for x in 0..width {
@@ -379,7 +517,117 @@ pub fn add_round_letterbox(
             new_img.put_pixel(x + padding.3 as u32, y + padding.0 as u32, *pixel);
         }
     }
-    
+
     Ok(Mat::new(image::DynamicImage::ImageRgb8(new_img)))
 }
 
+/// Resizes `img` to fit within `target_w`x`target_h` while preserving its
+/// aspect ratio, then centers it on a black canvas of exactly that size
+/// (the classic YOLO-style "letterbox"). Returns the resized+padded image
+/// together with an [`OpRecord`] so callers can map boxes back to the
+/// original image via [`map_boxes_to_original`].
+pub fn letterbox_to(
+    img: &Mat,
+    target_w: i32,
+    target_h: i32,
+) -> Result<(Mat, OpRecord), EngineError> {
+    let h = img.rows();
+    let w = img.cols();
+    if h <= 0 || w <= 0 || target_w <= 0 || target_h <= 0 {
+        return Err(EngineError::Preprocess("invalid image or target size".to_string()));
+    }
+
+    let scale = (target_w as f32 / w as f32).min(target_h as f32 / h as f32);
+    let new_w = ((w as f32) * scale).round().max(1.0) as i32;
+    let new_h = ((h as f32) * scale).round().max(1.0) as i32;
+
+    #[cfg(feature = "use-opencv")]
+    let resized = {
+        let mut d = Mat::default();
+        imgproc::resize(
+            img,
+            &mut d,
+            core::Size::new(new_w, new_h),
+            0.0,
+            0.0,
+            imgproc::INTER_LINEAR,
+        )?;
+        d
+    };
+
+    #[cfg(not(feature = "use-opencv"))]
+    let resized = {
+        let mut d = Mat::default();
+        image_impl::resize(img, &mut d, Size::new(new_w, new_h), INTER_LINEAR)?;
+        d
+    };
+
+    let pad_w = target_w - new_w;
+    let pad_h = target_h - new_h;
+    let top = pad_h / 2;
+    let left = pad_w / 2;
+    let padded = add_round_letterbox(&resized, (top, pad_h - top, left, pad_w - left))?;
+
+    let mut op_record = OpRecord::new();
+    let mut resize_m = BTreeMap::new();
+    resize_m.insert("ratio_h".to_string(), h as f32 / new_h as f32);
+    resize_m.insert("ratio_w".to_string(), w as f32 / new_w as f32);
+    op_record.insert("preprocess_1".to_string(), resize_m);
+
+    // `map_boxes_to_original` undoes "preprocess_1" before "padding_1", so
+    // the pad is recorded in original-image pixels.
+    let mut pad_m = BTreeMap::new();
+    pad_m.insert("top".to_string(), top as f32 * h as f32 / new_h as f32);
+    pad_m.insert("left".to_string(), left as f32 * w as f32 / new_w as f32);
+    op_record.insert("padding_1".to_string(), pad_m);
+
+    Ok((padded, op_record))
+}
+
+#[cfg(all(test, not(feature = "use-opencv")))]
+mod tests {
+    use super::*;
+
+    /// `letterbox_to`'s `OpRecord` should let `map_boxes_to_original` undo
+    /// both the resize and the centering pad: a box drawn at the resized
+    /// image's corners must map back to the original image's corners.
+    #[test]
+    fn test_letterbox_to_op_record_round_trips_through_map_boxes_to_original() {
+        let ori_w = 200;
+        let ori_h = 100;
+        let img = Mat::new(image::DynamicImage::ImageRgb8(image::RgbImage::from_pixel(
+            ori_w as u32,
+            ori_h as u32,
+            image::Rgb([0, 0, 0]),
+        )));
+
+        let (padded, op_record) = letterbox_to(&img, 150, 150).unwrap();
+        assert_eq!(padded.cols(), 150);
+        assert_eq!(padded.rows(), 150);
+
+        let resize_m = &op_record["preprocess_1"];
+        let ratio_w = resize_m["ratio_w"];
+        let ratio_h = resize_m["ratio_h"];
+        let pad_m = &op_record["padding_1"];
+        // The pad is recorded in original-image pixels; convert it back to
+        // the letterboxed image's pixels to place the box.
+        let top = pad_m["top"] / ratio_h;
+        let left = pad_m["left"] / ratio_w;
+        let new_w = ori_w as f32 / ratio_w;
+        let new_h = ori_h as f32 / ratio_h;
+
+        let mut boxes = [Quad::new([
+            Point2f::new(left, top),
+            Point2f::new(left + new_w, top),
+            Point2f::new(left + new_w, top + new_h),
+            Point2f::new(left, top + new_h),
+        ])];
+
+        map_boxes_to_original(&mut boxes, &op_record, ori_h, ori_w);
+
+        let corners = &boxes[0];
+        assert!((corners[0].x - 0.0).abs() < 1e-3 && (corners[0].y - 0.0).abs() < 1e-3);
+        assert!((corners[2].x - ori_w as f32).abs() < 1e-3 && (corners[2].y - ori_h as f32).abs() < 1e-3);
+    }
+}
+
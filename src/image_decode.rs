@@ -0,0 +1,275 @@
+//! Extension-based dispatch for image formats the `image` crate doesn't
+//! decode directly: HEIF/HEIC photos, camera RAW, and multi-page TIFF/PDF.
+//! Only used by the pure-Rust backend; the `use-opencv` backend decodes
+//! through OpenCV's own `imgcodecs` and isn't affected by this module.
+
+use std::path::Path;
+
+use image::DynamicImage;
+
+use crate::engine::EngineError;
+
+/// Decode `path` to its first (or only) page/frame, dispatching on the file
+/// extension. Falls back to `image::open` for anything not specifically
+/// handled here.
+pub fn decode_one(path: &Path) -> Result<DynamicImage, EngineError> {
+    let mut pages = decode_pages(path)?;
+    if pages.is_empty() {
+        return Err(EngineError::ImageError("decoded zero pages".to_string()));
+    }
+    Ok(pages.remove(0))
+}
+
+/// Decode every page/frame of `path`. Single-frame formats (including the
+/// default `image::open` fallback) return a one-element vector.
+pub fn decode_pages(path: &Path) -> Result<Vec<DynamicImage>, EngineError> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    match ext.as_str() {
+        #[cfg(feature = "heif")]
+        "heic" | "heif" => Ok(vec![decode_heif(path)?]),
+        #[cfg(feature = "raw")]
+        "raw" | "cr2" | "nef" | "arw" | "dng" | "raf" | "orf" => Ok(vec![decode_raw(path)?]),
+        "tif" | "tiff" => decode_tiff_pages(path),
+        "pdf" => decode_pdf_pages(path),
+        _ => {
+            let img = image::open(path).map_err(|e| EngineError::ImageError(e.to_string()))?;
+            Ok(vec![img])
+        }
+    }
+}
+
+#[cfg(feature = "heif")]
+fn decode_heif(path: &Path) -> Result<DynamicImage, EngineError> {
+    use libheif_rs::{ColorSpace, HeifContext, RgbChroma};
+
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| EngineError::ImageError("heif: non-UTF8 path".to_string()))?;
+    let ctx = HeifContext::read_from_file(path_str)
+        .map_err(|e| EngineError::ImageError(format!("heif: {e}")))?;
+    let handle = ctx
+        .primary_image_handle()
+        .map_err(|e| EngineError::ImageError(format!("heif: {e}")))?;
+    let image = handle
+        .decode(ColorSpace::Rgb(RgbChroma::Rgb), None)
+        .map_err(|e| EngineError::ImageError(format!("heif: {e}")))?;
+
+    let plane = image
+        .planes()
+        .interleaved
+        .ok_or_else(|| EngineError::ImageError("heif: expected interleaved RGB plane".to_string()))?;
+    let width = plane.width;
+    let height = plane.height;
+    let buf = plane.data.to_vec();
+
+    let img_buf = image::RgbImage::from_raw(width, height, buf)
+        .ok_or_else(|| EngineError::ImageError("heif: decoded buffer size mismatch".to_string()))?;
+    Ok(DynamicImage::ImageRgb8(img_buf))
+}
+
+#[cfg(feature = "raw")]
+fn decode_raw(path: &Path) -> Result<DynamicImage, EngineError> {
+    let raw = rawloader::decode_file(path).map_err(|e| EngineError::ImageError(format!("raw: {e}")))?;
+    demosaic_bilinear(&raw).ok_or_else(|| EngineError::ImageError("raw: unsupported CFA layout".to_string()))
+}
+
+/// Bilinear-interpolate a Bayer CFA sensor image into an RGB8 image. This is
+/// a quality/complexity tradeoff deliberately on the "simple" side: it's
+/// good enough to feed a text-detection/recognition pipeline (which doesn't
+/// need photographic fidelity), not a substitute for a full demosaic
+/// pipeline like AHD or VNG.
+#[cfg(feature = "raw")]
+fn demosaic_bilinear(raw: &rawloader::RawImage) -> Option<DynamicImage> {
+    use rawloader::CFA;
+
+    let width = raw.width;
+    let height = raw.height;
+    let data = match &raw.data {
+        rawloader::RawImageData::Integer(v) => v.clone(),
+        rawloader::RawImageData::Float(v) => v.iter().map(|&x| x as u16).collect(),
+    };
+
+    let cfa: &CFA = &raw.cfa;
+    let sample = |x: i64, y: i64, channel: usize| -> Option<f32> {
+        let x = x.clamp(0, width as i64 - 1) as usize;
+        let y = y.clamp(0, height as i64 - 1) as usize;
+        if cfa.color_at(y, x) != channel {
+            return None;
+        }
+        Some(data[y * width + x] as f32)
+    };
+
+    let mut out = vec![0u8; width * height * 3];
+    let max_val = raw.whitelevels.iter().copied().max().unwrap_or(65535).max(1) as f32;
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut rgb = [0f32; 3];
+            for (channel, value) in rgb.iter_mut().enumerate() {
+                if let Some(v) = sample(x as i64, y as i64, channel) {
+                    *value = v;
+                    continue;
+                }
+                let neighbors = [
+                    sample(x as i64 - 1, y as i64, channel),
+                    sample(x as i64 + 1, y as i64, channel),
+                    sample(x as i64, y as i64 - 1, channel),
+                    sample(x as i64, y as i64 + 1, channel),
+                    sample(x as i64 - 1, y as i64 - 1, channel),
+                    sample(x as i64 + 1, y as i64 - 1, channel),
+                    sample(x as i64 - 1, y as i64 + 1, channel),
+                    sample(x as i64 + 1, y as i64 + 1, channel),
+                ];
+                let found: Vec<f32> = neighbors.into_iter().flatten().collect();
+                *value = if found.is_empty() {
+                    0.0
+                } else {
+                    found.iter().sum::<f32>() / found.len() as f32
+                };
+            }
+
+            let idx = (y * width + x) * 3;
+            for c in 0..3 {
+                out[idx + c] = ((rgb[c] / max_val) * 255.0).clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+
+    let img_buf = image::RgbImage::from_raw(width as u32, height as u32, out)?;
+    Some(DynamicImage::ImageRgb8(img_buf))
+}
+
+fn decode_tiff_pages(path: &Path) -> Result<Vec<DynamicImage>, EngineError> {
+    use std::fs::File;
+    use std::io::BufReader;
+    use tiff::decoder::{Decoder, DecodingResult};
+
+    let file = File::open(path).map_err(|e| EngineError::ImageError(e.to_string()))?;
+    let mut decoder =
+        Decoder::new(BufReader::new(file)).map_err(|e| EngineError::ImageError(format!("tiff: {e}")))?;
+
+    let mut pages = Vec::new();
+    loop {
+        let (width, height) = decoder
+            .dimensions()
+            .map_err(|e| EngineError::ImageError(format!("tiff: {e}")))?;
+        let result = decoder
+            .read_image()
+            .map_err(|e| EngineError::ImageError(format!("tiff: {e}")))?;
+
+        let rgb: Vec<u8> = match result {
+            DecodingResult::U8(v) => v,
+            DecodingResult::U16(v) => v.into_iter().map(|x| (x >> 8) as u8).collect(),
+            _ => return Err(EngineError::ImageError("tiff: unsupported sample format".to_string())),
+        };
+
+        let pixel_count = width as usize * height as usize;
+        let img_buf = if rgb.len() == pixel_count * 3 {
+            image::RgbImage::from_raw(width, height, rgb)
+                .ok_or_else(|| EngineError::ImageError("tiff: decoded buffer size mismatch".to_string()))?
+        } else if rgb.len() == pixel_count {
+            // Grayscale page: the decoded buffer is one byte per pixel
+            // instead of three; expand it to RGB. Reuses the bytes this
+            // page's own `read_image()` call already produced above, rather
+            // than re-decoding the file (which would rewind to page 0).
+            let gray = image::GrayImage::from_raw(width, height, rgb)
+                .ok_or_else(|| EngineError::ImageError("tiff: decoded buffer size mismatch".to_string()))?;
+            image::DynamicImage::ImageLuma8(gray).to_rgb8()
+        } else {
+            return Err(EngineError::ImageError("tiff: decoded buffer size mismatch".to_string()));
+        };
+        pages.push(DynamicImage::ImageRgb8(img_buf));
+
+        if !decoder.more_images() {
+            break;
+        }
+        decoder
+            .next_image()
+            .map_err(|e| EngineError::ImageError(format!("tiff: {e}")))?;
+    }
+
+    Ok(pages)
+}
+
+fn decode_pdf_pages(path: &Path) -> Result<Vec<DynamicImage>, EngineError> {
+    use pdfium_render::prelude::*;
+
+    let pdfium = Pdfium::new(
+        Pdfium::bind_to_system_library()
+            .map_err(|e| EngineError::ImageError(format!("pdf: failed to bind pdfium: {e}")))?,
+    );
+    let doc = pdfium
+        .load_pdf_from_file(path, None)
+        .map_err(|e| EngineError::ImageError(format!("pdf: {e}")))?;
+
+    let render_config = PdfRenderConfig::new().set_target_width(2000);
+    let mut pages = Vec::new();
+    for page in doc.pages().iter() {
+        let bitmap = page
+            .render_with_config(&render_config)
+            .map_err(|e| EngineError::ImageError(format!("pdf: {e}")))?;
+        pages.push(bitmap.as_image());
+    }
+
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Write a two-page TIFF to a fresh temp file and return its path.
+    /// Page 0 is solid RGB8 `rgb_fill`; page 1 is solid Gray8
+    /// `gray_fill` so a regression that always returns page 0's bytes (the
+    /// bug `decode_tiff_pages` used to have for grayscale pages) is visible
+    /// as page 1 decoding back to `rgb_fill` instead of `gray_fill`.
+    fn write_test_tiff(name: &str, width: u32, height: u32, rgb_fill: [u8; 3], gray_fill: u8) -> std::path::PathBuf {
+        use tiff::encoder::{colortype, TiffEncoder};
+
+        let path = std::env::temp_dir().join(format!("rusto_test_{name}_{}.tiff", std::process::id()));
+        let mut buf = Vec::new();
+        {
+            let mut encoder = TiffEncoder::new(std::io::Cursor::new(&mut buf)).unwrap();
+
+            let rgb_data: Vec<u8> = (0..(width * height) as usize)
+                .flat_map(|_| rgb_fill)
+                .collect();
+            encoder
+                .write_image::<colortype::RGB8>(width, height, &rgb_data)
+                .unwrap();
+
+            let gray_data: Vec<u8> = vec![gray_fill; (width * height) as usize];
+            encoder
+                .write_image::<colortype::Gray8>(width, height, &gray_data)
+                .unwrap();
+        }
+
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(&buf).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_decode_tiff_pages_grayscale_second_page_is_not_page_zero() {
+        let path = write_test_tiff("grayscale_page", 4, 4, [200, 100, 50], 7);
+
+        let pages = decode_tiff_pages(&path).unwrap();
+        assert_eq!(pages.len(), 2);
+
+        let page0 = pages[0].to_rgb8();
+        assert_eq!(*page0.get_pixel(0, 0), image::Rgb([200, 100, 50]));
+
+        // Before the fix, this page silently re-decoded page 0 from a fresh
+        // file handle instead of expanding its own grayscale bytes.
+        let page1 = pages[1].to_rgb8();
+        assert_eq!(*page1.get_pixel(0, 0), image::Rgb([7, 7, 7]));
+
+        std::fs::remove_file(&path).ok();
+    }
+}
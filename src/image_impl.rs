@@ -1,4 +1,8 @@
 //! Image abstraction layer supporting both pure Rust and OpenCV backends
+//!
+//! The pure-Rust `resize` helper uses the `image` crate's resampling by
+//! default; enabling the `fast-resize` feature switches it to
+//! `fast_image_resize`'s SIMD-accelerated resizer instead.
 
 #[cfg(feature = "use-opencv")]
 pub use opencv_impl::*;
@@ -41,7 +45,7 @@ impl Size {
 #[cfg(not(feature = "use-opencv"))]
 mod rust_impl {
     use super::{Point2f, Size};
-    use image::{DynamicImage, GenericImageView, ImageBuffer, Rgb};
+    use image::{DynamicImage, GenericImageView, GrayImage, ImageBuffer, Luma, Rgb};
     use std::path::Path;
 
     pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -108,8 +112,111 @@ mod rust_impl {
         }
     }
 
+    /// Fetch pixel `(x, y)` as `[r, g, b]`, applying `color`'s handling of
+    /// grayscale/alpha sources. `Auto` inspects `img`'s underlying
+    /// `DynamicImage` variant; the explicit variants skip that detection.
+    /// `background` is the RGB color alpha is composited over.
+    pub fn sample_rgb(img: &Mat, x: i32, y: i32, color: crate::types::InputColor, background: [u8; 3]) -> Result<[u8; 3]> {
+        use crate::types::InputColor;
+
+        let (x, y) = (x as u32, y as u32);
+        let mode = match color {
+            InputColor::Auto => match &img.image {
+                DynamicImage::ImageLuma8(_) | DynamicImage::ImageLuma16(_) => InputColor::Gray,
+                DynamicImage::ImageLumaA8(_) | DynamicImage::ImageRgba8(_) | DynamicImage::ImageRgba16(_) => {
+                    InputColor::Rgba
+                }
+                _ => InputColor::Bgr,
+            },
+            other => other,
+        };
+
+        let pixel = img.image.get_pixel(x, y);
+        Ok(match mode {
+            InputColor::Gray => [pixel[0], pixel[0], pixel[0]],
+            InputColor::Rgba => {
+                let alpha = pixel[3] as f32 / 255.0;
+                let blend = |c: u8, bg: u8| (c as f32 * alpha + bg as f32 * (1.0 - alpha)).round() as u8;
+                [
+                    blend(pixel[0], background[0]),
+                    blend(pixel[1], background[1]),
+                    blend(pixel[2], background[2]),
+                ]
+            }
+            InputColor::Bgr | InputColor::Auto => [pixel[0], pixel[1], pixel[2]],
+        })
+    }
+
+    /// Bulk row-fetch variant of [`sample_rgb`]. Resolves `color`'s mode once
+    /// for the whole row rather than once per pixel, and reads straight out
+    /// of the backing `ImageBuffer`'s raw sample slice for the common 8-bit
+    /// variants instead of the bounds-checked `get_pixel` accessor, so the
+    /// normalization passes that call this per-row don't pay either cost per
+    /// pixel. Less common variants (16-bit, `LumaA8`, or an explicit color
+    /// override that doesn't match the buffer's native layout) fall back to
+    /// [`sample_rgb`] per pixel.
+    pub fn sample_rgb_row(
+        img: &Mat,
+        y: i32,
+        width: i32,
+        color: crate::types::InputColor,
+        background: [u8; 3],
+    ) -> Result<Vec<[u8; 3]>> {
+        use crate::types::InputColor;
+
+        let width = width as usize;
+        let mode = match color {
+            InputColor::Auto => match &img.image {
+                DynamicImage::ImageLuma8(_) | DynamicImage::ImageLuma16(_) => InputColor::Gray,
+                DynamicImage::ImageLumaA8(_) | DynamicImage::ImageRgba8(_) | DynamicImage::ImageRgba16(_) => {
+                    InputColor::Rgba
+                }
+                _ => InputColor::Bgr,
+            },
+            other => other,
+        };
+
+        match (&img.image, mode) {
+            (DynamicImage::ImageLuma8(buf), InputColor::Gray) => {
+                let w = buf.width() as usize;
+                let start = y as usize * w;
+                let row = &buf.as_raw()[start..start + w];
+                Ok(row[..width.min(w)].iter().map(|&v| [v, v, v]).collect())
+            }
+            (DynamicImage::ImageRgb8(buf), InputColor::Bgr | InputColor::Auto) => {
+                let w = buf.width() as usize;
+                let start = y as usize * w * 3;
+                let row = &buf.as_raw()[start..start + w * 3];
+                Ok(row[..width.min(w) * 3]
+                    .chunks_exact(3)
+                    .map(|p| [p[0], p[1], p[2]])
+                    .collect())
+            }
+            (DynamicImage::ImageRgba8(buf), InputColor::Rgba) => {
+                let w = buf.width() as usize;
+                let start = y as usize * w * 4;
+                let row = &buf.as_raw()[start..start + w * 4];
+                let blend = |c: u8, a: f32, bg: u8| (c as f32 * a + bg as f32 * (1.0 - a)).round() as u8;
+                Ok(row[..width.min(w) * 4]
+                    .chunks_exact(4)
+                    .map(|p| {
+                        let alpha = p[3] as f32 / 255.0;
+                        [
+                            blend(p[0], alpha, background[0]),
+                            blend(p[1], alpha, background[1]),
+                            blend(p[2], alpha, background[2]),
+                        ]
+                    })
+                    .collect())
+            }
+            _ => (0..width as i32)
+                .map(|x| sample_rgb(img, x, y, color, background))
+                .collect(),
+        }
+    }
+
     pub fn imread<P: AsRef<Path>>(path: P) -> Result<Mat> {
-        let img = image::open(path)?;
+        let img = crate::image_decode::decode_one(path.as_ref())?;
         Ok(Mat::new(img))
     }
 
@@ -119,6 +226,55 @@ mod rust_impl {
         Ok(())
     }
 
+    #[cfg(feature = "fast-resize")]
+    pub fn resize(
+        src: &Mat,
+        dst: &mut Mat,
+        dsize: Size,
+        interpolation: i32,
+    ) -> Result<()> {
+        use fast_image_resize as fr;
+
+        // Map OpenCV's codes onto fast_image_resize's closest convolution
+        // filter; every code other than INTER_CUBIC resizes bilinearly.
+        let filter = match interpolation {
+            2 => fr::FilterType::CatmullRom, // INTER_CUBIC
+            _ => fr::FilterType::Bilinear,   // INTER_LINEAR / default
+        };
+
+        let src_rgb = src.to_rgb8();
+        let (src_w, src_h) = src_rgb.dimensions();
+        let src_image = fr::images::Image::from_vec_u8(
+            src_w,
+            src_h,
+            src_rgb.into_raw(),
+            fr::PixelType::U8x3,
+        )?;
+
+        let mut dst_image = fr::images::Image::new(
+            dsize.width.max(1) as u32,
+            dsize.height.max(1) as u32,
+            fr::PixelType::U8x3,
+        );
+
+        let mut resizer = fr::Resizer::new();
+        resizer.resize(
+            &src_image,
+            &mut dst_image,
+            &fr::ResizeOptions::new().resize_alg(fr::ResizeAlg::Convolution(filter)),
+        )?;
+
+        let buf = ImageBuffer::<Rgb<u8>, _>::from_raw(
+            dst_image.width(),
+            dst_image.height(),
+            dst_image.into_vec(),
+        )
+        .ok_or("fast-resize produced a buffer of the wrong size")?;
+        *dst = Mat::new(DynamicImage::ImageRgb8(buf));
+        Ok(())
+    }
+
+    #[cfg(not(feature = "fast-resize"))]
     pub fn resize(
         src: &Mat,
         dst: &mut Mat,
@@ -131,7 +287,7 @@ mod rust_impl {
             2 => image::imageops::FilterType::CatmullRom,   // INTER_CUBIC
             _ => image::imageops::FilterType::Triangle,      // Default to bilinear
         };
-        
+
         let resized = src
             .image
             .resize_exact(dsize.width as u32, dsize.height as u32, filter);
@@ -154,15 +310,443 @@ mod rust_impl {
         Ok(())
     }
 
+    // Structuring element shapes, matching OpenCV's `MorphShapes`.
+    pub const MORPH_RECT: i32 = 0;
+    pub const MORPH_CROSS: i32 = 1;
+    pub const MORPH_ELLIPSE: i32 = 2;
+
+    // Compound morphology ops, matching OpenCV's `MorphTypes`.
+    pub const MORPH_OPEN: i32 = 2;
+    pub const MORPH_CLOSE: i32 = 3;
+
+    /// Build a structuring element of `size`, matching OpenCV's
+    /// `getStructuringElement`: a rectangle fills the whole box, a cross
+    /// sets only the center row and column, and an ellipse rasterizes the
+    /// filled ellipse inscribed in the box. Represented as a single-channel
+    /// `Mat` with active cells at 255 and inactive cells at 0, so `erode`
+    /// and `dilate` can read it back with `kernel_offsets`.
+    pub fn get_structuring_element(shape: i32, size: Size) -> Result<Mat> {
+        let w = size.width.max(1) as u32;
+        let h = size.height.max(1) as u32;
+        let cx = (w - 1) as f32 / 2.0;
+        let cy = (h - 1) as f32 / 2.0;
+        let rx = (w as f32 / 2.0).max(1e-6);
+        let ry = (h as f32 / 2.0).max(1e-6);
+
+        let mut element = GrayImage::new(w, h);
+        for y in 0..h {
+            for x in 0..w {
+                let active = match shape {
+                    MORPH_CROSS => x == cx.round() as u32 || y == cy.round() as u32,
+                    MORPH_ELLIPSE => {
+                        let nx = (x as f32 - cx) / rx;
+                        let ny = (y as f32 - cy) / ry;
+                        nx * nx + ny * ny <= 1.0
+                    }
+                    _ => true, // MORPH_RECT and anything unrecognized: filled box
+                };
+                element.put_pixel(x, y, Luma([if active { 255 } else { 0 }]));
+            }
+        }
+        Ok(Mat::new(DynamicImage::ImageLuma8(element)))
+    }
+
+    /// Pixel offsets (relative to the kernel's center) of `kernel`'s active
+    /// cells, used by `erode`/`dilate` to know which neighbors to sample.
+    fn kernel_offsets(kernel: &Mat) -> Vec<(i32, i32)> {
+        let gray = kernel.image.to_luma8();
+        let (w, h) = gray.dimensions();
+        let (cx, cy) = (w as i32 / 2, h as i32 / 2);
+
+        let mut offsets = Vec::new();
+        for y in 0..h {
+            for x in 0..w {
+                if gray.get_pixel(x, y)[0] > 0 {
+                    offsets.push((x as i32 - cx, y as i32 - cy));
+                }
+            }
+        }
+        offsets
+    }
+
+    /// One erosion/dilation pass over `src` with border pixels replicated
+    /// (matching `BORDER_REPLICATE`). Grayscale images are processed in a
+    /// single channel; anything else is processed per RGB channel.
+    fn morph_pass(src: &DynamicImage, offsets: &[(i32, i32)], take_max: bool) -> DynamicImage {
+        let clamp = |v: i32, max: u32| v.clamp(0, max as i32 - 1) as u32;
+
+        if let DynamicImage::ImageLuma8(gray) = src {
+            let (w, h) = gray.dimensions();
+            let mut out = GrayImage::new(w, h);
+            for y in 0..h as i32 {
+                for x in 0..w as i32 {
+                    let mut acc = if take_max { 0u8 } else { 255u8 };
+                    for &(dx, dy) in offsets {
+                        let v = gray.get_pixel(clamp(x + dx, w), clamp(y + dy, h))[0];
+                        acc = if take_max { acc.max(v) } else { acc.min(v) };
+                    }
+                    out.put_pixel(x as u32, y as u32, Luma([acc]));
+                }
+            }
+            return DynamicImage::ImageLuma8(out);
+        }
+
+        let rgb = src.to_rgb8();
+        let (w, h) = rgb.dimensions();
+        let mut out = ImageBuffer::<Rgb<u8>, Vec<u8>>::new(w, h);
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                let mut acc = if take_max { [0u8; 3] } else { [255u8; 3] };
+                for &(dx, dy) in offsets {
+                    let p = rgb.get_pixel(clamp(x + dx, w), clamp(y + dy, h));
+                    for c in 0..3 {
+                        acc[c] = if take_max { acc[c].max(p[c]) } else { acc[c].min(p[c]) };
+                    }
+                }
+                out.put_pixel(x as u32, y as u32, Rgb(acc));
+            }
+        }
+        DynamicImage::ImageRgb8(out)
+    }
+
+    /// Erode `src` with `kernel`, matching OpenCV's `erode`: each output
+    /// pixel becomes the minimum source value over the kernel's active
+    /// offsets centered on that pixel. Out-of-bounds samples replicate the
+    /// nearest edge pixel.
+    pub fn erode(src: &Mat, dst: &mut Mat, kernel: &Mat, iterations: i32) -> Result<()> {
+        let offsets = kernel_offsets(kernel);
+        let mut current = src.image.clone();
+        for _ in 0..iterations.max(1) {
+            current = morph_pass(&current, &offsets, false);
+        }
+        *dst = Mat::new(current);
+        Ok(())
+    }
+
+    /// Dilate `src` with `kernel`, matching OpenCV's `dilate`: each output
+    /// pixel becomes the maximum source value over the kernel's active
+    /// offsets centered on that pixel.
+    pub fn dilate(src: &Mat, dst: &mut Mat, kernel: &Mat, iterations: i32) -> Result<()> {
+        let offsets = kernel_offsets(kernel);
+        let mut current = src.image.clone();
+        for _ in 0..iterations.max(1) {
+            current = morph_pass(&current, &offsets, true);
+        }
+        *dst = Mat::new(current);
+        Ok(())
+    }
+
+    /// Compound morphology, matching OpenCV's `morphologyEx`: `MORPH_OPEN`
+    /// erodes then dilates (removes small bright specks), `MORPH_CLOSE`
+    /// dilates then erodes (fills small dark gaps).
+    pub fn morphology_ex(src: &Mat, dst: &mut Mat, op: i32, kernel: &Mat) -> Result<()> {
+        match op {
+            MORPH_OPEN => {
+                let mut tmp = Mat::default();
+                erode(src, &mut tmp, kernel, 1)?;
+                dilate(&tmp, dst, kernel, 1)?;
+            }
+            MORPH_CLOSE => {
+                let mut tmp = Mat::default();
+                dilate(src, &mut tmp, kernel, 1)?;
+                erode(&tmp, dst, kernel, 1)?;
+            }
+            _ => return Err("Unsupported morphology op".into()),
+        }
+        Ok(())
+    }
+
+    /// Canny edge detection, matching OpenCV's `Canny`. Converts to
+    /// grayscale, Gaussian-blurs to suppress noise, computes Sobel gradient
+    /// magnitude/orientation, thins edges with non-maximum suppression, then
+    /// keeps only edges connected (8-neighbor) to a pixel above
+    /// `high_thresh`, discarding everything below `low_thresh` and
+    /// everything in between that isn't reachable from a strong edge.
+    /// `aperture` is accepted for signature parity with OpenCV but only the
+    /// 3x3 Sobel kernel is implemented. Output is a single-channel 0/255 Mat.
+    pub fn canny(src: &Mat, dst: &mut Mat, low_thresh: f64, high_thresh: f64, _aperture: i32) -> Result<()> {
+        let gray = src.image.to_luma8();
+        let (w, h) = gray.dimensions();
+        if w == 0 || h == 0 {
+            *dst = Mat::new(DynamicImage::ImageLuma8(gray));
+            return Ok(());
+        }
+
+        let blurred = gaussian_blur_5x5(&gray);
+
+        let sample = |x: i32, y: i32| -> f32 {
+            let cx = x.clamp(0, w as i32 - 1) as u32;
+            let cy = y.clamp(0, h as i32 - 1) as u32;
+            blurred.get_pixel(cx, cy)[0] as f32
+        };
+
+        let mut magnitude = vec![0f32; (w * h) as usize];
+        let mut angle = vec![0f32; (w * h) as usize];
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                let gx = sample(x + 1, y - 1) + 2.0 * sample(x + 1, y) + sample(x + 1, y + 1)
+                    - sample(x - 1, y - 1)
+                    - 2.0 * sample(x - 1, y)
+                    - sample(x - 1, y + 1);
+                let gy = sample(x - 1, y + 1) + 2.0 * sample(x, y + 1) + sample(x + 1, y + 1)
+                    - sample(x - 1, y - 1)
+                    - 2.0 * sample(x, y - 1)
+                    - sample(x + 1, y - 1);
+                let idx = (y as u32 * w + x as u32) as usize;
+                magnitude[idx] = (gx * gx + gy * gy).sqrt();
+                angle[idx] = gy.atan2(gx).to_degrees();
+            }
+        }
+
+        // Non-maximum suppression: keep a pixel only if its magnitude is the
+        // local maximum along its (quantized) gradient direction.
+        let mut suppressed = vec![0f32; (w * h) as usize];
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                let idx = (y as u32 * w + x as u32) as usize;
+                let mag = magnitude[idx];
+                if mag == 0.0 {
+                    continue;
+                }
+
+                // Quantize to 0/45/90/135 degrees.
+                let mut a = angle[idx] % 180.0;
+                if a < 0.0 {
+                    a += 180.0;
+                }
+                let (dx1, dy1, dx2, dy2) = if !(22.5..157.5).contains(&a) {
+                    (1, 0, -1, 0) // 0 degrees: horizontal gradient, compare left/right
+                } else if a < 67.5 {
+                    (1, 1, -1, -1) // 45 degrees (y grows downward)
+                } else if a < 112.5 {
+                    (0, 1, 0, -1) // 90 degrees: vertical gradient, compare up/down
+                } else {
+                    (1, -1, -1, 1) // 135 degrees
+                };
+
+                let get_mag = |dx: i32, dy: i32| -> f32 {
+                    let nx = x + dx;
+                    let ny = y + dy;
+                    if nx < 0 || ny < 0 || nx >= w as i32 || ny >= h as i32 {
+                        0.0
+                    } else {
+                        magnitude[(ny as u32 * w + nx as u32) as usize]
+                    }
+                };
+
+                if mag >= get_mag(dx1, dy1) && mag >= get_mag(dx2, dy2) {
+                    suppressed[idx] = mag;
+                }
+            }
+        }
+
+        // Hysteresis: start from strong edges and flood-fill (8-connected)
+        // through weak-but-above-low-threshold pixels.
+        let low = low_thresh as f32;
+        let high = high_thresh as f32;
+        let mut edges = vec![false; (w * h) as usize];
+        let mut stack: Vec<(i32, i32)> = Vec::new();
+
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                let idx = (y as u32 * w + x as u32) as usize;
+                if suppressed[idx] >= high && !edges[idx] {
+                    edges[idx] = true;
+                    stack.push((x, y));
+                }
+            }
+        }
+
+        while let Some((x, y)) = stack.pop() {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if dx == 0 && dy == 0 {
+                        continue;
+                    }
+                    let nx = x + dx;
+                    let ny = y + dy;
+                    if nx < 0 || ny < 0 || nx >= w as i32 || ny >= h as i32 {
+                        continue;
+                    }
+                    let nidx = (ny as u32 * w + nx as u32) as usize;
+                    if !edges[nidx] && suppressed[nidx] >= low {
+                        edges[nidx] = true;
+                        stack.push((nx, ny));
+                    }
+                }
+            }
+        }
+
+        let mut out = GrayImage::new(w, h);
+        for y in 0..h {
+            for x in 0..w {
+                let idx = (y * w + x) as usize;
+                out.put_pixel(x, y, Luma([if edges[idx] { 255 } else { 0 }]));
+            }
+        }
+
+        *dst = Mat::new(DynamicImage::ImageLuma8(out));
+        Ok(())
+    }
+
+    /// Separable 5x5 Gaussian blur (sigma ~= 1.0), the noise-reduction step
+    /// Canny runs before gradient estimation. Border pixels clamp to the
+    /// nearest edge instead of padding with zero.
+    fn gaussian_blur_5x5(src: &GrayImage) -> GrayImage {
+        const KERNEL: [f32; 5] = [1.0, 4.0, 6.0, 4.0, 1.0];
+        const KERNEL_SUM: f32 = 16.0;
+
+        let (w, h) = src.dimensions();
+        let sample = |x: i32, y: i32| -> f32 {
+            let cx = x.clamp(0, w as i32 - 1) as u32;
+            let cy = y.clamp(0, h as i32 - 1) as u32;
+            src.get_pixel(cx, cy)[0] as f32
+        };
+
+        let mut horiz = vec![0f32; (w * h) as usize];
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                let mut acc = 0f32;
+                for (k, &wk) in KERNEL.iter().enumerate() {
+                    acc += wk * sample(x + k as i32 - 2, y);
+                }
+                horiz[(y as u32 * w + x as u32) as usize] = acc / KERNEL_SUM;
+            }
+        }
+
+        let mut out = GrayImage::new(w, h);
+        for y in 0..h as i32 {
+            for x in 0..w as i32 {
+                let mut acc = 0f32;
+                for (k, &wk) in KERNEL.iter().enumerate() {
+                    let sy = (y + k as i32 - 2).clamp(0, h as i32 - 1);
+                    acc += wk * horiz[(sy as u32 * w + x as u32) as usize];
+                }
+                out.put_pixel(x as u32, y as u32, Luma([(acc / KERNEL_SUM).round() as u8]));
+            }
+        }
+
+        out
+    }
+
+    /// Reflects an out-of-range index at the boundary with the edge pixel
+    /// duplicated, matching OpenCV's `BORDER_REFLECT` (not `_101`): for a
+    /// 4-pixel row, indices run `...,1,0,0,1,2,3,3,2,1,0,0,...`.
+    fn reflect_index(v: i32, len: i32) -> i32 {
+        if len <= 1 {
+            return 0;
+        }
+        let period = 2 * len;
+        let mut m = v % period;
+        if m < 0 {
+            m += period;
+        }
+        if m < len {
+            m
+        } else {
+            period - 1 - m
+        }
+    }
+
+    /// Sample `src_img` at `(x, y)`, applying `border_mode` when the
+    /// coordinate falls outside the image: `BORDER_CONSTANT` reads as black,
+    /// `BORDER_REPLICATE` clamps to the nearest edge pixel, `BORDER_REFLECT`
+    /// mirrors the index at the boundary. Any other mode returns `None`,
+    /// signaling the caller to fall back to `sample_bilinear`'s legacy
+    /// skip/nearest-neighbor behavior.
+    fn get_border_pixel(
+        src_img: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        border_mode: i32,
+    ) -> Option<Rgb<u8>> {
+        if x >= 0 && x < w && y >= 0 && y < h {
+            return Some(*src_img.get_pixel(x as u32, y as u32));
+        }
+
+        match border_mode {
+            BORDER_CONSTANT => Some(Rgb([0, 0, 0])),
+            BORDER_REPLICATE => {
+                let rx = x.clamp(0, w - 1) as u32;
+                let ry = y.clamp(0, h - 1) as u32;
+                Some(*src_img.get_pixel(rx, ry))
+            }
+            BORDER_REFLECT => {
+                let rx = reflect_index(x, w) as u32;
+                let ry = reflect_index(y, h) as u32;
+                Some(*src_img.get_pixel(rx, ry))
+            }
+            _ => None,
+        }
+    }
+
+    /// Bilinear-sample `src_img` at `(x_f, y_f)`, matching OpenCV's default
+    /// `INTER_LINEAR`. Each of the four surrounding corners is read through
+    /// `get_border_pixel`, so `BORDER_CONSTANT`/`BORDER_REPLICATE`/
+    /// `BORDER_REFLECT` always produce a full blend; for any other
+    /// `border_mode`, an out-of-bounds corner falls back to the original
+    /// behavior (nearest-neighbor if the floor corner is in bounds,
+    /// otherwise the destination pixel is left untouched).
+    fn sample_bilinear(
+        src_img: &ImageBuffer<Rgb<u8>, Vec<u8>>,
+        x_f: f32,
+        y_f: f32,
+        src_cols: i32,
+        src_rows: i32,
+        border_mode: i32,
+    ) -> Option<Rgb<u8>> {
+        let x0 = x_f.floor() as i32;
+        let y0 = y_f.floor() as i32;
+        let fx = x_f - x0 as f32;
+        let fy = y_f - y0 as f32;
+
+        let corner = |cx: i32, cy: i32| get_border_pixel(src_img, cx, cy, src_cols, src_rows, border_mode);
+        let (p00, p10, p01, p11) = (
+            corner(x0, y0),
+            corner(x0 + 1, y0),
+            corner(x0, y0 + 1),
+            corner(x0 + 1, y0 + 1),
+        );
+
+        if let (Some(p00), Some(p10), Some(p01), Some(p11)) = (p00, p10, p01, p11) {
+            let blend = |c: usize| -> u8 {
+                ((1.0 - fx) * (1.0 - fy) * p00[c] as f32
+                    + fx * (1.0 - fy) * p10[c] as f32
+                    + (1.0 - fx) * fy * p01[c] as f32
+                    + fx * fy * p11[c] as f32) as u8
+            };
+            return Some(Rgb([blend(0), blend(1), blend(2)]));
+        }
+
+        if x0 >= 0 && x0 < src_cols && y0 >= 0 && y0 < src_rows {
+            Some(*src_img.get_pixel(x0 as u32, y0 as u32))
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(feature = "simd-warp"))]
     pub fn warp_perspective(
+        src: &Mat,
+        dst: &mut Mat,
+        matrix: &[[f64; 3]; 3],
+        dsize: Size,
+        flags: i32,
+        border_mode: i32,
+    ) -> Result<()> {
+        warp_perspective_scalar(src, dst, matrix, dsize, flags, border_mode)
+    }
+
+    fn warp_perspective_scalar(
         src: &Mat,
         dst: &mut Mat,
         matrix: &[[f64; 3]; 3],
         dsize: Size,
         _flags: i32,
-        _border_mode: i32,
+        border_mode: i32,
     ) -> Result<()> {
-        // Create output image
         let mut out_img = ImageBuffer::new(dsize.width as u32, dsize.height as u32);
         let src_img = src.to_rgb8();
 
@@ -175,59 +759,215 @@ mod rust_impl {
         let (m20, m21, m22) = (m_inv[2][0], m_inv[2][1], m_inv[2][2]);
         let src_cols = src.cols();
         let src_rows = src.rows();
-        
+
         for y in 0..dsize.height as u32 {
             let y_f = y as f64;
-            // Pre-compute y-dependent terms
             let m01y = m01 * y_f;
             let m11y = m11 * y_f;
             let m21y = m21 * y_f;
-            
+
             for x in 0..dsize.width as u32 {
-                // Apply inverse transform with homogeneous coordinates
                 let x_f = x as f64;
                 let src_x_h = m00 * x_f + m01y + m02;
                 let src_y_h = m10 * x_f + m11y + m12;
                 let w = m20 * x_f + m21y + m22;
 
-                let src_x_f = src_x_h / w;
-                let src_y_f = src_y_h / w;
-
-                // Bilinear interpolation (matches OpenCV's default INTER_LINEAR)
-                let x0 = src_x_f.floor() as i32;
-                let y0 = src_y_f.floor() as i32;
-                let x1 = x0 + 1;
-                let y1 = y0 + 1;
-
-                // Check bounds for all 4 corners
-                if x0 >= 0 && x1 < src_cols && y0 >= 0 && y1 < src_rows {
-                    let fx = src_x_f - x0 as f64;
-                    let fy = src_y_f - y0 as f64;
-
-                    let p00 = src_img.get_pixel(x0 as u32, y0 as u32);
-                    let p10 = src_img.get_pixel(x1 as u32, y0 as u32);
-                    let p01 = src_img.get_pixel(x0 as u32, y1 as u32);
-                    let p11 = src_img.get_pixel(x1 as u32, y1 as u32);
-
-                    // Bilinear interpolation for each channel
-                    let r = ((1.0 - fx) * (1.0 - fy) * p00[0] as f64
-                        + fx * (1.0 - fy) * p10[0] as f64
-                        + (1.0 - fx) * fy * p01[0] as f64
-                        + fx * fy * p11[0] as f64) as u8;
-                    let g = ((1.0 - fx) * (1.0 - fy) * p00[1] as f64
-                        + fx * (1.0 - fy) * p10[1] as f64
-                        + (1.0 - fx) * fy * p01[1] as f64
-                        + fx * fy * p11[1] as f64) as u8;
-                    let b = ((1.0 - fx) * (1.0 - fy) * p00[2] as f64
-                        + fx * (1.0 - fy) * p10[2] as f64
-                        + (1.0 - fx) * fy * p01[2] as f64
-                        + fx * fy * p11[2] as f64) as u8;
-
-                    out_img.put_pixel(x, y, image::Rgb([r, g, b]));
-                } else if x0 >= 0 && x0 < src_cols && y0 >= 0 && y0 < src_rows {
-                    // Fallback to nearest neighbor at edges
-                    let pixel = src_img.get_pixel(x0 as u32, y0 as u32);
-                    out_img.put_pixel(x, y, *pixel);
+                let src_x_f = (src_x_h / w) as f32;
+                let src_y_f = (src_y_h / w) as f32;
+
+                if let Some(pixel) =
+                    sample_bilinear(&src_img, src_x_f, src_y_f, src_cols, src_rows, border_mode)
+                {
+                    out_img.put_pixel(x, y, pixel);
+                }
+            }
+        }
+
+        *dst = Mat::new(DynamicImage::ImageRgb8(out_img));
+        Ok(())
+    }
+
+    /// SIMD variant of `warp_perspective`: computes the inverse-mapped
+    /// source coordinates (and their homogeneous divide) for four
+    /// destination pixels at once with `wide::f32x4`, then samples and
+    /// blends each of the four lanes individually (bilinear interpolation
+    /// itself needs 4 scattered texture reads per lane, so it isn't
+    /// vectorized further). A scalar tail handles row widths not divisible
+    /// by four.
+    ///
+    /// This path does the inverse-homography divide in `f32` throughout,
+    /// while `warp_perspective_scalar` accumulates in `f64` before a late
+    /// cast, so the two are *not* bit-exact: `test_warp_perspective_simd_matches_scalar_within_tolerance`
+    /// checks they agree within a few levels per channel, not pixel-for-pixel.
+    #[cfg(feature = "simd-warp")]
+    pub fn warp_perspective(
+        src: &Mat,
+        dst: &mut Mat,
+        matrix: &[[f64; 3]; 3],
+        dsize: Size,
+        flags: i32,
+        border_mode: i32,
+    ) -> Result<()> {
+        warp_perspective_simd(src, dst, matrix, dsize, flags, border_mode)
+    }
+
+    #[cfg(feature = "simd-warp")]
+    fn warp_perspective_simd(
+        src: &Mat,
+        dst: &mut Mat,
+        matrix: &[[f64; 3]; 3],
+        dsize: Size,
+        _flags: i32,
+        border_mode: i32,
+    ) -> Result<()> {
+        use wide::f32x4;
+
+        let width = dsize.width.max(0) as u32;
+        let height = dsize.height.max(0) as u32;
+        let mut out_img = ImageBuffer::new(width, height);
+        let src_img = src.to_rgb8();
+
+        let m_inv = invert_matrix_3x3(matrix)?;
+        let (m00, m01, m02) = (m_inv[0][0] as f32, m_inv[0][1] as f32, m_inv[0][2] as f32);
+        let (m10, m11, m12) = (m_inv[1][0] as f32, m_inv[1][1] as f32, m_inv[1][2] as f32);
+        let (m20, m21, m22) = (m_inv[2][0] as f32, m_inv[2][1] as f32, m_inv[2][2] as f32);
+        let src_cols = src.cols();
+        let src_rows = src.rows();
+
+        let m00_v = f32x4::splat(m00);
+        let m10_v = f32x4::splat(m10);
+        let m20_v = f32x4::splat(m20);
+        let lane_offsets = f32x4::from([0.0, 1.0, 2.0, 3.0]);
+
+        for y in 0..height {
+            let y_f = y as f32;
+            let c0 = m01 * y_f + m02;
+            let c1 = m11 * y_f + m12;
+            let c2 = m21 * y_f + m22;
+            let c0_v = f32x4::splat(c0);
+            let c1_v = f32x4::splat(c1);
+            let c2_v = f32x4::splat(c2);
+
+            let mut x = 0u32;
+            while x + 4 <= width {
+                let xs = f32x4::splat(x as f32) + lane_offsets;
+                let src_x_h = m00_v * xs + c0_v;
+                let src_y_h = m10_v * xs + c1_v;
+                let w_h = m20_v * xs + c2_v;
+                let inv_w = f32x4::splat(1.0) / w_h;
+                let src_x = (src_x_h * inv_w).to_array();
+                let src_y = (src_y_h * inv_w).to_array();
+
+                for lane in 0..4u32 {
+                    if let Some(pixel) = sample_bilinear(
+                        &src_img,
+                        src_x[lane as usize],
+                        src_y[lane as usize],
+                        src_cols,
+                        src_rows,
+                        border_mode,
+                    ) {
+                        out_img.put_pixel(x + lane, y, pixel);
+                    }
+                }
+
+                x += 4;
+            }
+
+            // Scalar tail for the remaining (< 4) pixels in this row.
+            while x < width {
+                let x_f = x as f32;
+                let src_x_f = (m00 * x_f + c0) / (m20 * x_f + c2);
+                let src_y_f = (m10 * x_f + c1) / (m20 * x_f + c2);
+                if let Some(pixel) =
+                    sample_bilinear(&src_img, src_x_f, src_y_f, src_cols, src_rows, border_mode)
+                {
+                    out_img.put_pixel(x, y, pixel);
+                }
+                x += 1;
+            }
+        }
+
+        *dst = Mat::new(DynamicImage::ImageRgb8(out_img));
+        Ok(())
+    }
+
+    /// Solve for a 3-point affine transform, matching OpenCV's
+    /// `getAffineTransform`: two point correspondences give two linear
+    /// equations each (`u = a*x + b*y + c`, `v = d*x + e*y + f`), so three
+    /// correspondences give a fully-determined 6x6 system. Solved with
+    /// nalgebra's `lu()`, the same path `get_perspective_transform` uses for
+    /// its 8-parameter system. Returned as a full 3x3 matrix with the third
+    /// row fixed to `[0, 0, 1]` so it composes with the same inverse/apply
+    /// code `warp_perspective` uses.
+    pub fn get_affine_transform(src_pts: &[[f32; 2]; 3], dst_pts: &[[f32; 2]; 3]) -> Result<[[f64; 3]; 3]> {
+        use nalgebra::DMatrix;
+
+        let mut a = DMatrix::<f64>::zeros(6, 6);
+        let mut b = DMatrix::<f64>::zeros(6, 1);
+
+        for i in 0..3 {
+            let x = src_pts[i][0] as f64;
+            let y = src_pts[i][1] as f64;
+            let u = dst_pts[i][0] as f64;
+            let v = dst_pts[i][1] as f64;
+
+            a[(i, 0)] = x;
+            a[(i, 1)] = y;
+            a[(i, 2)] = 1.0;
+            b[(i, 0)] = u;
+
+            a[(i + 3, 3)] = x;
+            a[(i + 3, 4)] = y;
+            a[(i + 3, 5)] = 1.0;
+            b[(i + 3, 0)] = v;
+        }
+
+        let lu = a.lu().solve(&b).ok_or("Affine system is singular")?;
+
+        Ok([
+            [lu[(0, 0)], lu[(1, 0)], lu[(2, 0)]],
+            [lu[(3, 0)], lu[(4, 0)], lu[(5, 0)]],
+            [0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Affine warp, matching OpenCV's `warpAffine`: like `warp_perspective`
+    /// but the inverse mapping has no homogeneous divide, since an affine
+    /// matrix's third row is always `[0, 0, 1]`. Cheaper and more
+    /// numerically stable than `warp_perspective` for the common case of a
+    /// 3-point (rather than 4-point) rectification.
+    pub fn warp_affine(
+        src: &Mat,
+        dst: &mut Mat,
+        matrix: &[[f64; 3]; 3],
+        dsize: Size,
+        _flags: i32,
+        border_mode: i32,
+    ) -> Result<()> {
+        let mut out_img = ImageBuffer::new(dsize.width as u32, dsize.height as u32);
+        let src_img = src.to_rgb8();
+
+        let m_inv = invert_matrix_3x3(matrix)?;
+        let (m00, m01, m02) = (m_inv[0][0], m_inv[0][1], m_inv[0][2]);
+        let (m10, m11, m12) = (m_inv[1][0], m_inv[1][1], m_inv[1][2]);
+        let src_cols = src.cols();
+        let src_rows = src.rows();
+
+        for y in 0..dsize.height as u32 {
+            let y_f = y as f64;
+            let m01y = m01 * y_f;
+            let m11y = m11 * y_f;
+
+            for x in 0..dsize.width as u32 {
+                let x_f = x as f64;
+                let src_x_f = (m00 * x_f + m01y + m02) as f32;
+                let src_y_f = (m10 * x_f + m11y + m12) as f32;
+
+                if let Some(pixel) =
+                    sample_bilinear(&src_img, src_x_f, src_y_f, src_cols, src_rows, border_mode)
+                {
+                    out_img.put_pixel(x, y, pixel);
                 }
             }
         }
@@ -342,8 +1082,15 @@ mod rust_impl {
         ])
     }
 
+    /// Minimum-area bounding rectangle of `contour`, matching OpenCV's
+    /// `minAreaRect`. Builds the convex hull, then walks the rotating
+    /// calipers over each hull edge: for every edge direction, project all
+    /// hull points onto that direction and its perpendicular, and keep the
+    /// axis-aligned-in-that-frame rectangle with the smallest area. The
+    /// true minimum-area rectangle always has one side flush with a hull
+    /// edge, so this check over all edges is exhaustive and needs no
+    /// further search.
     pub fn min_area_rect(contour: &[Point2f]) -> Result<(Point2f, Size, f32)> {
-        // Rotating calipers algorithm to find minimum area bounding box
         if contour.is_empty() {
             return Err("Empty contour".into());
         }
@@ -531,12 +1278,298 @@ mod rust_impl {
         })
     }
 
+    /// Extract contours from a binary mask, matching OpenCV's
+    /// `findContours`. `mask` is thresholded at the same `> 127` boundary
+    /// `crate::contours` uses elsewhere; `method` is accepted for API
+    /// parity but only `ChainApproxMethod::None` is implemented, so every
+    /// traced boundary pixel is returned with no compression.
+    pub fn find_contours(
+        mask: &Mat,
+        mode: crate::contours::RetrievalMode,
+        _method: crate::contours::ChainApproxMethod,
+    ) -> Result<Vec<Vec<Point2f>>> {
+        let gray = mask.image.to_luma8();
+        let (contours, _hierarchy) = crate::contours::find_contours_with_mode(&gray, mode);
+        Ok(contours
+            .into_iter()
+            .map(|c| {
+                c.points
+                    .into_iter()
+                    .map(|(x, y)| Point2f::new(x as f32, y as f32))
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Ramer-Douglas-Peucker simplification of `curve`, matching OpenCV's
+    /// `approxPolyDP`: find the point farthest (perpendicular distance) from
+    /// the chord through the curve's two endpoints; if that distance exceeds
+    /// `epsilon`, keep the point and recurse on both halves, otherwise
+    /// collapse the whole span to just its endpoints. `closed` treats the
+    /// curve as a loop: it's first split into two open chains at its
+    /// farthest-apart point pair so the recursion doesn't get an arbitrary,
+    /// simplification-biasing start point. Pairs naturally with
+    /// `find_contours` to reduce a traced contour to the few vertices
+    /// `min_area_rect`/`get_perspective_transform` need. The recursion
+    /// itself lives in `crate::rdp`, shared with `contours::approx_poly_dp`
+    /// and `postprocess::approx_poly_dp`.
+    pub fn approx_poly_dp(curve: &[Point2f], epsilon: f32, closed: bool) -> Vec<Point2f> {
+        if curve.len() < 3 {
+            return curve.to_vec();
+        }
+
+        let as_f64: Vec<(f64, f64)> = curve.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+        let simplified = if closed {
+            crate::rdp::simplify_closed(&as_f64, epsilon as f64)
+        } else {
+            crate::rdp::simplify_open(&as_f64, epsilon as f64)
+        };
+        simplified
+            .into_iter()
+            .map(|(x, y)| Point2f::new(x as f32, y as f32))
+            .collect()
+    }
+
     // Constants for compatibility
     pub const INTER_LINEAR: i32 = 1;
     #[allow(dead_code)]
     pub const INTER_CUBIC: i32 = 2;
+    pub const BORDER_CONSTANT: i32 = 0;
     pub const BORDER_REPLICATE: i32 = 1;
+    pub const BORDER_REFLECT: i32 = 2;
     pub const ROTATE_90_CLOCKWISE: i32 = 0;
+    pub const ROTATE_180: i32 = 1;
+
+    #[cfg(all(test, feature = "simd-warp"))]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_warp_perspective_simd_matches_scalar_within_tolerance() {
+            let mut src_img = ImageBuffer::new(16, 16);
+            for y in 0..16u32 {
+                for x in 0..16u32 {
+                    src_img.put_pixel(x, y, Rgb([(x * 16) as u8, (y * 16) as u8, (x + y) as u8]));
+                }
+            }
+            let src = Mat::new(DynamicImage::ImageRgb8(src_img));
+
+            // A mild perspective skew, not just an affine transform, so both
+            // paths exercise the homogeneous divide.
+            let matrix = [
+                [1.05, 0.08, -1.5],
+                [0.03, 1.1, -2.0],
+                [0.0003, 0.0002, 1.0],
+            ];
+            let dsize = Size::new(16, 16);
+
+            let mut scalar_out = Mat::default();
+            warp_perspective_scalar(&src, &mut scalar_out, &matrix, dsize, 0, BORDER_REPLICATE)
+                .unwrap();
+            let mut simd_out = Mat::default();
+            warp_perspective_simd(&src, &mut simd_out, &matrix, dsize, 0, BORDER_REPLICATE).unwrap();
+
+            let scalar_img = scalar_out.to_rgb8();
+            let simd_img = simd_out.to_rgb8();
+            assert_eq!(scalar_img.dimensions(), simd_img.dimensions());
+
+            // f32-throughout (SIMD) vs f64-then-cast (scalar) accumulation
+            // can round a sampled pixel by a level or two; anything larger
+            // would mean the two paths disagree on where they're sampling
+            // from, not just how precisely.
+            const TOLERANCE: i16 = 2;
+            for (a, b) in scalar_img.pixels().zip(simd_img.pixels()) {
+                for c in 0..3 {
+                    let diff = (a[c] as i16 - b[c] as i16).abs();
+                    assert!(
+                        diff <= TOLERANCE,
+                        "channel {c} differs by {diff} (scalar={a:?}, simd={b:?})"
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod morphology_tests {
+        use super::*;
+
+        #[test]
+        fn test_get_structuring_element_shapes() {
+            let rect = get_structuring_element(MORPH_RECT, Size::new(3, 3)).unwrap();
+            let rect_gray = rect.image.to_luma8();
+            assert!(rect_gray.pixels().all(|p| p[0] == 255), "rect fills every cell");
+
+            let cross = get_structuring_element(MORPH_CROSS, Size::new(3, 3)).unwrap();
+            let cross_gray = cross.image.to_luma8();
+            assert_eq!(cross_gray.get_pixel(0, 0)[0], 0, "cross corner is inactive");
+            assert_eq!(cross_gray.get_pixel(1, 1)[0], 255, "cross center is active");
+            assert_eq!(cross_gray.get_pixel(1, 0)[0], 255, "cross top-middle is active");
+
+            let ellipse = get_structuring_element(MORPH_ELLIPSE, Size::new(5, 5)).unwrap();
+            let ellipse_gray = ellipse.image.to_luma8();
+            assert_eq!(ellipse_gray.get_pixel(2, 2)[0], 255, "ellipse center is active");
+            assert_eq!(ellipse_gray.get_pixel(0, 0)[0], 0, "ellipse corner is inactive");
+        }
+
+        fn single_bright_pixel(size: u32) -> Mat {
+            let mut img = GrayImage::new(size, size);
+            img.put_pixel(size / 2, size / 2, Luma([255]));
+            Mat::new(DynamicImage::ImageLuma8(img))
+        }
+
+        #[test]
+        fn test_dilate_grows_a_single_bright_pixel() {
+            let src = single_bright_pixel(7);
+            let kernel = get_structuring_element(MORPH_RECT, Size::new(3, 3)).unwrap();
+            let mut dst = Mat::default();
+            dilate(&src, &mut dst, &kernel, 1).unwrap();
+
+            let dst_gray = dst.image.to_luma8();
+            // Every cell in the 3x3 neighborhood of the center should now be bright.
+            for dy in -1i32..=1 {
+                for dx in -1i32..=1 {
+                    let (x, y) = ((3 + dx) as u32, (3 + dy) as u32);
+                    assert_eq!(dst_gray.get_pixel(x, y)[0], 255, "({x},{y}) should be dilated bright");
+                }
+            }
+            // Corners untouched by the kernel stay dark.
+            assert_eq!(dst_gray.get_pixel(0, 0)[0], 0);
+        }
+
+        #[test]
+        fn test_erode_removes_a_single_bright_pixel() {
+            // A single bright pixel has no 3x3 neighborhood that's fully lit,
+            // so eroding it with a 3x3 rect kernel should darken everything.
+            let src = single_bright_pixel(7);
+            let kernel = get_structuring_element(MORPH_RECT, Size::new(3, 3)).unwrap();
+            let mut dst = Mat::default();
+            erode(&src, &mut dst, &kernel, 1).unwrap();
+
+            let dst_gray = dst.image.to_luma8();
+            assert!(dst_gray.pixels().all(|p| p[0] == 0));
+        }
+
+        #[test]
+        fn test_morphology_ex_open_removes_isolated_speck_but_close_fills_hole() {
+            let kernel = get_structuring_element(MORPH_RECT, Size::new(3, 3)).unwrap();
+
+            // Open (erode->dilate) should erase a speck too small for the kernel.
+            let speck = single_bright_pixel(7);
+            let mut opened = Mat::default();
+            morphology_ex(&speck, &mut opened, MORPH_OPEN, &kernel).unwrap();
+            assert!(opened.image.to_luma8().pixels().all(|p| p[0] == 0));
+
+            // Close (dilate->erode) should fill a single dark pixel punched
+            // into an otherwise bright region.
+            let mut filled_img = GrayImage::new(7, 7);
+            for p in filled_img.pixels_mut() {
+                *p = Luma([255]);
+            }
+            filled_img.put_pixel(3, 3, Luma([0]));
+            let hole = Mat::new(DynamicImage::ImageLuma8(filled_img));
+            let mut closed = Mat::default();
+            morphology_ex(&hole, &mut closed, MORPH_CLOSE, &kernel).unwrap();
+            assert_eq!(closed.image.to_luma8().get_pixel(3, 3)[0], 255);
+        }
+    }
+
+    #[cfg(test)]
+    mod canny_tests {
+        use super::*;
+
+        #[test]
+        fn test_canny_uniform_image_has_no_edges() {
+            let img = GrayImage::from_pixel(20, 20, Luma([128]));
+            let src = Mat::new(DynamicImage::ImageLuma8(img));
+            let mut dst = Mat::default();
+            canny(&src, &mut dst, 50.0, 150.0, 3).unwrap();
+
+            let out = dst.image.to_luma8();
+            assert!(out.pixels().all(|p| p[0] == 0), "flat image should have no edges");
+        }
+
+        #[test]
+        fn test_canny_detects_a_sharp_vertical_step() {
+            let mut img = GrayImage::new(20, 20);
+            for y in 0..20u32 {
+                for x in 0..20u32 {
+                    img.put_pixel(x, y, Luma([if x < 10 { 0 } else { 255 }]));
+                }
+            }
+            let src = Mat::new(DynamicImage::ImageLuma8(img));
+            let mut dst = Mat::default();
+            canny(&src, &mut dst, 50.0, 150.0, 3).unwrap();
+
+            let out = dst.image.to_luma8();
+            // The step sits at x=10; away from the top/bottom border, some
+            // pixel near that column on every row should be marked an edge.
+            for y in 3..17u32 {
+                let row_has_edge = (7..13u32).any(|x| out.get_pixel(x, y)[0] == 255);
+                assert!(row_has_edge, "row {y} should have an edge near the step");
+            }
+            // Far from the step, both flat regions should be edge-free.
+            for y in 3..17u32 {
+                assert_eq!(out.get_pixel(1, y)[0], 0);
+                assert_eq!(out.get_pixel(18, y)[0], 0);
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod warp_affine_border_tests {
+        use super::*;
+
+        fn striped_src() -> Mat {
+            // 5x5 image: the last column is a distinct color from the rest,
+            // so BORDER_REPLICATE's edge-clamp is distinguishable from
+            // BORDER_CONSTANT's zero-fill once we sample past the right edge.
+            let mut img = ImageBuffer::new(5, 5);
+            for y in 0..5u32 {
+                for x in 0..5u32 {
+                    let color = if x == 4 { Rgb([10, 20, 30]) } else { Rgb([200, 100, 50]) };
+                    img.put_pixel(x, y, color);
+                }
+            }
+            Mat::new(DynamicImage::ImageRgb8(img))
+        }
+
+        const IDENTITY: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+        #[test]
+        fn test_warp_affine_border_constant_fills_out_of_bounds_black() {
+            let src = striped_src();
+            let mut dst = Mat::default();
+            warp_affine(&src, &mut dst, &IDENTITY, Size::new(8, 5), 0, BORDER_CONSTANT).unwrap();
+
+            let out = dst.to_rgb8();
+            // Columns 0..5 come straight from the source; columns 5..8 are
+            // entirely out of bounds and must be filled with black.
+            for y in 0..5u32 {
+                assert_eq!(*out.get_pixel(0, y), Rgb([200, 100, 50]));
+                assert_eq!(*out.get_pixel(4, y), Rgb([10, 20, 30]));
+                for x in 5..8u32 {
+                    assert_eq!(*out.get_pixel(x, y), Rgb([0, 0, 0]), "({x},{y}) should be constant-filled");
+                }
+            }
+        }
+
+        #[test]
+        fn test_warp_affine_border_replicate_clamps_to_edge_pixel() {
+            let src = striped_src();
+            let mut dst = Mat::default();
+            warp_affine(&src, &mut dst, &IDENTITY, Size::new(8, 5), 0, BORDER_REPLICATE).unwrap();
+
+            let out = dst.to_rgb8();
+            // Out-of-bounds columns should replicate the source's rightmost
+            // column (10, 20, 30), not fall back to black.
+            for y in 0..5u32 {
+                for x in 5..8u32 {
+                    assert_eq!(*out.get_pixel(x, y), Rgb([10, 20, 30]), "({x},{y}) should replicate the edge column");
+                }
+            }
+        }
+    }
 }
 
 // OpenCV implementation
@@ -544,15 +1577,27 @@ mod rust_impl {
 mod opencv_impl {
     use super::{Point2f, Size};
     pub use opencv::core::Mat;
-    pub use opencv::imgcodecs::{imread as cv_imread, imwrite as cv_imwrite, IMREAD_COLOR};
+    pub use opencv::imgcodecs::{
+        imdecode as cv_imdecode, imread as cv_imread, imwrite as cv_imwrite, IMREAD_COLOR,
+    };
     pub use opencv::imgproc::{
-        get_perspective_transform, resize as cv_resize, warp_perspective as cv_warp_perspective,
-        INTER_CUBIC, INTER_LINEAR,
+        dilate as cv_dilate, erode as cv_erode, get_affine_transform,
+        get_perspective_transform, get_structuring_element as cv_get_structuring_element,
+        morphology_ex as cv_morphology_ex, resize as cv_resize,
+        warp_affine as cv_warp_affine, warp_perspective as cv_warp_perspective, MorphShapes,
+        MorphTypes, INTER_CUBIC, INTER_LINEAR,
     };
     pub use opencv::core::{rotate as cv_rotate, BorderTypes, RotateFlags};
-    
+
     pub const BORDER_REPLICATE: i32 = BorderTypes::BORDER_REPLICATE as i32;
     pub const ROTATE_90_CLOCKWISE: i32 = RotateFlags::ROTATE_90_CLOCKWISE as i32;
+    pub const ROTATE_180: i32 = RotateFlags::ROTATE_180 as i32;
+    pub const MORPH_RECT: i32 = MorphShapes::MORPH_RECT as i32;
+    pub const MORPH_CROSS: i32 = MorphShapes::MORPH_CROSS as i32;
+    pub const MORPH_ELLIPSE: i32 = MorphShapes::MORPH_ELLIPSE as i32;
+    pub const MORPH_OPEN: i32 = MorphTypes::MORPH_OPEN as i32;
+    pub const MORPH_CLOSE: i32 = MorphTypes::MORPH_CLOSE as i32;
+    use opencv::prelude::*;
     use std::path::Path;
 
     pub type Result<T> = opencv::Result<T>;
@@ -561,6 +1606,129 @@ mod opencv_impl {
         cv_imread(path.as_ref().to_str().unwrap(), IMREAD_COLOR)
     }
 
+    /// Fetch pixel `(x, y)` as `[r, g, b]`, applying `color`'s handling of
+    /// grayscale/alpha sources. `Auto` inspects `img`'s channel count; the
+    /// explicit variants skip that detection. `background` is the RGB color
+    /// alpha is composited over. Mirrors `rust_impl::sample_rgb`.
+    pub fn sample_rgb(img: &Mat, x: i32, y: i32, color: crate::types::InputColor, background: [u8; 3]) -> Result<[u8; 3]> {
+        use crate::types::InputColor;
+
+        let mode = match color {
+            InputColor::Auto => match img.channels() {
+                1 => InputColor::Gray,
+                4 => InputColor::Rgba,
+                _ => InputColor::Bgr,
+            },
+            other => other,
+        };
+
+        Ok(match mode {
+            InputColor::Gray => {
+                let v = *img.at_2d::<u8>(y, x)?;
+                [v, v, v]
+            }
+            InputColor::Rgba => {
+                let pix = img.at_2d::<opencv::core::Vec4b>(y, x)?;
+                let alpha = pix[3] as f32 / 255.0;
+                let blend = |c: u8, bg: u8| (c as f32 * alpha + bg as f32 * (1.0 - alpha)).round() as u8;
+                // BGRA storage order: pix[0]=B, pix[1]=G, pix[2]=R, pix[3]=A.
+                [
+                    blend(pix[2], background[0]),
+                    blend(pix[1], background[1]),
+                    blend(pix[0], background[2]),
+                ]
+            }
+            InputColor::Bgr | InputColor::Auto => {
+                let pix = img.at_2d::<opencv::core::Vec3b>(y, x)?;
+                [pix[2], pix[1], pix[0]]
+            }
+        })
+    }
+
+    /// Bulk row-fetch variant of [`sample_rgb`]. Resolves `color`'s mode once
+    /// for the whole row rather than once per pixel, and reads the row via
+    /// `Mat::at_row` instead of a bounds-checked `at_2d` call per pixel.
+    /// Mirrors `rust_impl::sample_rgb_row`.
+    pub fn sample_rgb_row(
+        img: &Mat,
+        y: i32,
+        width: i32,
+        color: crate::types::InputColor,
+        background: [u8; 3],
+    ) -> Result<Vec<[u8; 3]>> {
+        use crate::types::InputColor;
+
+        let mode = match color {
+            InputColor::Auto => match img.channels() {
+                1 => InputColor::Gray,
+                4 => InputColor::Rgba,
+                _ => InputColor::Bgr,
+            },
+            other => other,
+        };
+
+        let width = width as usize;
+        Ok(match mode {
+            InputColor::Gray => {
+                let row = img.at_row::<u8>(y)?;
+                row[..width.min(row.len())].iter().map(|&v| [v, v, v]).collect()
+            }
+            InputColor::Rgba => {
+                let row = img.at_row::<opencv::core::Vec4b>(y)?;
+                let blend = |c: u8, a: f32, bg: u8| (c as f32 * a + bg as f32 * (1.0 - a)).round() as u8;
+                row[..width.min(row.len())]
+                    .iter()
+                    .map(|pix| {
+                        let alpha = pix[3] as f32 / 255.0;
+                        // BGRA storage order: pix[0]=B, pix[1]=G, pix[2]=R, pix[3]=A.
+                        [
+                            blend(pix[2], alpha, background[0]),
+                            blend(pix[1], alpha, background[1]),
+                            blend(pix[0], alpha, background[2]),
+                        ]
+                    })
+                    .collect()
+            }
+            InputColor::Bgr | InputColor::Auto => {
+                let row = img.at_row::<opencv::core::Vec3b>(y)?;
+                row[..width.min(row.len())]
+                    .iter()
+                    .map(|pix| [pix[2], pix[1], pix[0]])
+                    .collect()
+            }
+        })
+    }
+
+    /// Decode an encoded image (JPEG/PNG/etc.) held in memory, with no disk
+    /// round-trip. Mirrors `imread`, but for bytes that never touched a path.
+    pub fn imdecode_bytes(data: &[u8]) -> Result<Mat> {
+        let buf = opencv::core::Vector::from_slice(data);
+        cv_imdecode(&buf, IMREAD_COLOR)
+    }
+
+    /// Convert an already-decoded `image::DynamicImage` into a BGR `Mat`,
+    /// with no disk round-trip. Counterpart to `imdecode_bytes` for callers
+    /// who already hold pixels (e.g. from the `image` crate or an `ndarray`
+    /// tensor) rather than encoded bytes.
+    pub fn mat_from_dynamic(img: &image::DynamicImage) -> Result<Mat> {
+        let rgb = img.to_rgb8();
+        let (w, h) = rgb.dimensions();
+        let mut mat = Mat::new_rows_cols_with_default(
+            h as i32,
+            w as i32,
+            opencv::core::CV_8UC3,
+            opencv::core::Scalar::all(0.0),
+        )?;
+        for y in 0..h {
+            for x in 0..w {
+                let p = rgb.get_pixel(x, y);
+                *mat.at_2d_mut::<opencv::core::Vec3b>(y as i32, x as i32)? =
+                    opencv::core::Vec3b::from([p[2], p[1], p[0]]);
+            }
+        }
+        Ok(mat)
+    }
+
     pub fn imwrite<P: AsRef<Path>>(path: P, img: &Mat) -> Result<()> {
         cv_imwrite(
             path.as_ref().to_str().unwrap(),
@@ -585,6 +1753,51 @@ mod opencv_impl {
         cv_rotate(src, dst, rotation)
     }
 
+    pub fn get_structuring_element(shape: i32, size: Size) -> Result<Mat> {
+        cv_get_structuring_element(
+            shape,
+            opencv::core::Size::new(size.width, size.height),
+            opencv::core::Point::new(-1, -1),
+        )
+    }
+
+    pub fn erode(src: &Mat, dst: &mut Mat, kernel: &Mat, iterations: i32) -> Result<()> {
+        cv_erode(
+            src,
+            dst,
+            kernel,
+            opencv::core::Point::new(-1, -1),
+            iterations,
+            BORDER_REPLICATE,
+            opencv::imgproc::morphology_default_border_value()?,
+        )
+    }
+
+    pub fn dilate(src: &Mat, dst: &mut Mat, kernel: &Mat, iterations: i32) -> Result<()> {
+        cv_dilate(
+            src,
+            dst,
+            kernel,
+            opencv::core::Point::new(-1, -1),
+            iterations,
+            BORDER_REPLICATE,
+            opencv::imgproc::morphology_default_border_value()?,
+        )
+    }
+
+    pub fn morphology_ex(src: &Mat, dst: &mut Mat, op: i32, kernel: &Mat) -> Result<()> {
+        cv_morphology_ex(
+            src,
+            dst,
+            op,
+            kernel,
+            opencv::core::Point::new(-1, -1),
+            1,
+            BORDER_REPLICATE,
+            opencv::imgproc::morphology_default_border_value()?,
+        )
+    }
+
     pub fn warp_perspective(
         src: &Mat,
         dst: &mut Mat,
@@ -604,6 +1817,25 @@ mod opencv_impl {
         )
     }
 
+    pub fn warp_affine(
+        src: &Mat,
+        dst: &mut Mat,
+        matrix: &Mat,
+        dsize: Size,
+        flags: i32,
+        border_mode: i32,
+    ) -> Result<()> {
+        cv_warp_affine(
+            src,
+            dst,
+            matrix,
+            opencv::core::Size::new(dsize.width, dsize.height),
+            flags,
+            border_mode,
+            opencv::core::Scalar::all(0.0),
+        )
+    }
+
     // Convert Point2f to opencv::core::Point2f
     impl From<Point2f> for opencv::core::Point2f {
         fn from(p: Point2f) -> Self {
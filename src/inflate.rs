@@ -0,0 +1,624 @@
+//! Minimal RFC 1950 zlib / RFC 1951 deflate decoder.
+//!
+//! Used to transparently load `.gz`/`.zlib`-compressed model files without
+//! pulling in a C zlib dependency. Only decompression is implemented, since
+//! model files are produced offline.
+
+use crate::engine::EngineError;
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    byte_pos: usize,
+    bit_pos: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            byte_pos: 0,
+            bit_pos: 0,
+        }
+    }
+
+    fn read_bit(&mut self) -> Result<u32, EngineError> {
+        let byte = *self
+            .data
+            .get(self.byte_pos)
+            .ok_or_else(|| EngineError::Preprocess("inflate: unexpected end of stream".to_string()))?;
+        let bit = (byte >> self.bit_pos) & 1;
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+        Ok(bit as u32)
+    }
+
+    fn read_bits(&mut self, n: u32) -> Result<u32, EngineError> {
+        let mut value = 0u32;
+        for i in 0..n {
+            value |= self.read_bit()? << i;
+        }
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        if self.bit_pos != 0 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+    }
+
+    fn read_u16_le(&mut self) -> Result<u16, EngineError> {
+        self.align_to_byte();
+        let lo = *self
+            .data
+            .get(self.byte_pos)
+            .ok_or_else(|| EngineError::Preprocess("inflate: unexpected end of stream".to_string()))?;
+        let hi = *self
+            .data
+            .get(self.byte_pos + 1)
+            .ok_or_else(|| EngineError::Preprocess("inflate: unexpected end of stream".to_string()))?;
+        self.byte_pos += 2;
+        Ok((lo as u16) | ((hi as u16) << 8))
+    }
+}
+
+/// Canonical Huffman decode table built from a list of code lengths, indexed
+/// by symbol (0 = symbol unused).
+struct HuffmanTree {
+    // (code length, code value) -> symbol, looked up by walking bit-by-bit.
+    counts: Vec<u16>,
+    symbols: Vec<u16>,
+}
+
+impl HuffmanTree {
+    fn from_lengths(lengths: &[u8]) -> Self {
+        let max_bits = lengths.iter().cloned().max().unwrap_or(0) as usize;
+        let mut counts = vec![0u16; max_bits + 1];
+        for &len in lengths {
+            if len > 0 {
+                counts[len as usize] += 1;
+            }
+        }
+
+        let mut offsets = vec![0u16; max_bits + 2];
+        for len in 1..=max_bits {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+
+        let mut symbols = vec![0u16; offsets[max_bits + 1] as usize];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len > 0 {
+                let off = offsets[len as usize] as usize;
+                symbols[off] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, EngineError> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+
+        for len in 1..self.counts.len() {
+            code |= reader.read_bit()? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + (code - first)) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+
+        Err(EngineError::Preprocess(
+            "inflate: invalid Huffman code".to_string(),
+        ))
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn fixed_huffman_trees() -> (HuffmanTree, HuffmanTree) {
+    let mut lit_lengths = [0u8; 288];
+    for (i, len) in lit_lengths.iter_mut().enumerate() {
+        *len = if i < 144 {
+            8
+        } else if i < 256 {
+            9
+        } else if i < 280 {
+            7
+        } else {
+            8
+        };
+    }
+    let dist_lengths = [5u8; 30];
+
+    (
+        HuffmanTree::from_lengths(&lit_lengths),
+        HuffmanTree::from_lengths(&dist_lengths),
+    )
+}
+
+fn read_dynamic_huffman_trees(
+    reader: &mut BitReader,
+) -> Result<(HuffmanTree, HuffmanTree), EngineError> {
+    let hlit = reader.read_bits(5)? as usize + 257;
+    let hdist = reader.read_bits(5)? as usize + 1;
+    let hclen = reader.read_bits(4)? as usize + 4;
+
+    let mut code_length_lengths = [0u8; 19];
+    for &order in CODE_LENGTH_ORDER.iter().take(hclen) {
+        code_length_lengths[order] = reader.read_bits(3)? as u8;
+    }
+    let code_length_tree = HuffmanTree::from_lengths(&code_length_lengths);
+
+    let mut lengths = Vec::with_capacity(hlit + hdist);
+    while lengths.len() < hlit + hdist {
+        let symbol = code_length_tree.decode(reader)?;
+        match symbol {
+            0..=15 => lengths.push(symbol as u8),
+            16 => {
+                let prev = *lengths
+                    .last()
+                    .ok_or_else(|| EngineError::Preprocess("inflate: bad repeat code".to_string()))?;
+                let repeat = reader.read_bits(2)? + 3;
+                for _ in 0..repeat {
+                    lengths.push(prev);
+                }
+            }
+            17 => {
+                let repeat = reader.read_bits(3)? + 3;
+                for _ in 0..repeat {
+                    lengths.push(0);
+                }
+            }
+            18 => {
+                let repeat = reader.read_bits(7)? + 11;
+                for _ in 0..repeat {
+                    lengths.push(0);
+                }
+            }
+            _ => {
+                return Err(EngineError::Preprocess(
+                    "inflate: invalid code-length symbol".to_string(),
+                ))
+            }
+        }
+    }
+
+    let lit_lengths = &lengths[..hlit];
+    let dist_lengths = &lengths[hlit..hlit + hdist];
+
+    Ok((
+        HuffmanTree::from_lengths(lit_lengths),
+        HuffmanTree::from_lengths(dist_lengths),
+    ))
+}
+
+/// Decode a raw RFC 1951 deflate stream (no zlib/gzip framing).
+fn inflate_raw(data: &[u8]) -> Result<Vec<u8>, EngineError> {
+    inflate_raw_with_dict(data, None)
+}
+
+/// Decode a raw RFC 1951 deflate stream, seeding the sliding window with
+/// `preset_dict` (if given) before decoding so back-references in the
+/// stream can point into it, the same way zlib's `deflateSetDictionary`
+/// preset dictionaries work. Lets a domain-specific character dictionary be
+/// delta-compressed against a shared base list instead of carrying its own
+/// copy of the common entries.
+pub fn inflate_raw_with_dict(data: &[u8], preset_dict: Option<&[u8]>) -> Result<Vec<u8>, EngineError> {
+    let mut reader = BitReader::new(data);
+    let mut out = Vec::new();
+    if let Some(dict) = preset_dict {
+        out.extend_from_slice(dict);
+    }
+    let dict_len = out.len();
+
+    loop {
+        let is_final = reader.read_bits(1)? == 1;
+        let block_type = reader.read_bits(2)?;
+
+        match block_type {
+            0 => {
+                // Stored (uncompressed) block.
+                reader.align_to_byte();
+                let len = reader.read_u16_le()?;
+                let _nlen = reader.read_u16_le()?;
+                for _ in 0..len {
+                    let byte = *data
+                        .get(reader.byte_pos)
+                        .ok_or_else(|| EngineError::Preprocess("inflate: truncated stored block".to_string()))?;
+                    out.push(byte);
+                    reader.byte_pos += 1;
+                }
+            }
+            1 | 2 => {
+                let (lit_tree, dist_tree) = if block_type == 1 {
+                    fixed_huffman_trees()
+                } else {
+                    read_dynamic_huffman_trees(&mut reader)?
+                };
+
+                loop {
+                    let symbol = lit_tree.decode(&mut reader)?;
+                    if symbol < 256 {
+                        out.push(symbol as u8);
+                    } else if symbol == 256 {
+                        break;
+                    } else {
+                        let idx = (symbol - 257) as usize;
+                        if idx >= LENGTH_BASE.len() {
+                            return Err(EngineError::Preprocess(
+                                "inflate: invalid length symbol".to_string(),
+                            ));
+                        }
+                        let length = LENGTH_BASE[idx] as usize
+                            + reader.read_bits(LENGTH_EXTRA[idx] as u32)? as usize;
+
+                        let dist_symbol = dist_tree.decode(&mut reader)? as usize;
+                        if dist_symbol >= DIST_BASE.len() {
+                            return Err(EngineError::Preprocess(
+                                "inflate: invalid distance symbol".to_string(),
+                            ));
+                        }
+                        let distance = DIST_BASE[dist_symbol] as usize
+                            + reader.read_bits(DIST_EXTRA[dist_symbol] as u32)? as usize;
+
+                        if distance > out.len() {
+                            return Err(EngineError::Preprocess(
+                                "inflate: back-reference beyond window".to_string(),
+                            ));
+                        }
+                        let start = out.len() - distance;
+                        for i in 0..length {
+                            let byte = out[start + i];
+                            out.push(byte);
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Err(EngineError::Preprocess(
+                    "inflate: reserved block type".to_string(),
+                ))
+            }
+        }
+
+        if is_final {
+            break;
+        }
+    }
+
+    Ok(out.split_off(dict_len))
+}
+
+/// Inflate an RFC 1950 zlib stream: 2-byte CMF/FLG header, deflate payload,
+/// 4-byte Adler-32 trailer (not verified).
+pub fn inflate_zlib(data: &[u8]) -> Result<Vec<u8>, EngineError> {
+    inflate_zlib_with_dict(data, None)
+}
+
+/// Same as [`inflate_zlib`], but seeds the deflate window with `preset_dict`
+/// (see [`inflate_raw_with_dict`]).
+pub fn inflate_zlib_with_dict(data: &[u8], preset_dict: Option<&[u8]>) -> Result<Vec<u8>, EngineError> {
+    if data.len() < 6 {
+        return Err(EngineError::Preprocess(
+            "inflate: zlib stream too short".to_string(),
+        ));
+    }
+    let cmf = data[0];
+    if cmf & 0x0f != 8 {
+        return Err(EngineError::Preprocess(
+            "inflate: unsupported zlib compression method".to_string(),
+        ));
+    }
+    inflate_raw_with_dict(&data[2..data.len() - 4], preset_dict)
+}
+
+/// Returns true if `data` starts with a zlib (RFC 1950) header.
+pub fn is_zlib(data: &[u8]) -> bool {
+    data.len() >= 2 && (data[0] & 0x0f) == 8 && (((data[0] as u16) << 8 | data[1] as u16) % 31 == 0)
+}
+
+/// Returns true if `data` starts with a gzip (RFC 1952) magic header.
+pub fn is_gzip(data: &[u8]) -> bool {
+    data.len() >= 2 && data[0] == 0x1f && data[1] == 0x8b
+}
+
+/// Inflate a gzip (RFC 1952) member: 10-byte-minimum header with optional
+/// extra/name/comment fields, deflate payload, 8-byte CRC32/size trailer.
+pub fn inflate_gzip(data: &[u8]) -> Result<Vec<u8>, EngineError> {
+    inflate_gzip_with_dict(data, None)
+}
+
+/// Same as [`inflate_gzip`], but seeds the deflate window with `preset_dict`
+/// (see [`inflate_raw_with_dict`]).
+pub fn inflate_gzip_with_dict(data: &[u8], preset_dict: Option<&[u8]>) -> Result<Vec<u8>, EngineError> {
+    if data.len() < 10 || !is_gzip(data) {
+        return Err(EngineError::Preprocess(
+            "inflate: not a gzip stream".to_string(),
+        ));
+    }
+    let flags = data[3];
+    let mut pos = 10usize;
+
+    if flags & 0x04 != 0 {
+        // FEXTRA
+        let xlen = u16::from_le_bytes([data[pos], data[pos + 1]]) as usize;
+        pos += 2 + xlen;
+    }
+    if flags & 0x08 != 0 {
+        // FNAME
+        while data.get(pos).is_some_and(|&b| b != 0) {
+            pos += 1;
+        }
+        pos += 1;
+    }
+    if flags & 0x10 != 0 {
+        // FCOMMENT
+        while data.get(pos).is_some_and(|&b| b != 0) {
+            pos += 1;
+        }
+        pos += 1;
+    }
+    if flags & 0x02 != 0 {
+        // FHCRC
+        pos += 2;
+    }
+
+    if data.len() < pos + 8 {
+        return Err(EngineError::Preprocess(
+            "inflate: truncated gzip stream".to_string(),
+        ));
+    }
+
+    inflate_raw_with_dict(&data[pos..data.len() - 8], preset_dict)
+}
+
+/// Transparently inflate a `.gz`/`.zlib`-compressed file (detected by
+/// extension or magic header) into memory. Returns `None` when the file
+/// isn't compressed, so the caller falls back to reading it as plain text
+/// instead of paying for an extra read of a potentially large asset. Shared
+/// by model loading (`engine::load_model_bytes`) and character dictionary
+/// loading (`rec::CtcDecoder::from_cfg`).
+pub fn maybe_inflate_file(path: &std::path::Path) -> Result<Option<Vec<u8>>, EngineError> {
+    maybe_inflate_file_with_dict(path, None)
+}
+
+/// Same as [`maybe_inflate_file`], but seeds the deflate window with
+/// `preset_dict` when the file turns out to be compressed. Used by
+/// `rec::CtcDecoder::from_cfg` to decompress a language-specific character
+/// dictionary that was delta-compressed against `rec::BASE_CHAR_DICT`
+/// instead of carrying its own copy of the shared entries.
+pub fn maybe_inflate_file_with_dict(
+    path: &std::path::Path,
+    preset_dict: Option<&[u8]>,
+) -> Result<Option<Vec<u8>>, EngineError> {
+    let ext_is_compressed = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("gz") | Some("zlib")
+    );
+
+    if !ext_is_compressed {
+        // Still sniff the magic header in case the file is compressed
+        // without a matching extension.
+        let mut header = [0u8; 4];
+        let len = {
+            use std::io::Read;
+            let mut f = std::fs::File::open(path).map_err(|e| EngineError::Preprocess(e.to_string()))?;
+            f.read(&mut header).map_err(|e| EngineError::Preprocess(e.to_string()))?
+        };
+        let header = &header[..len];
+        if !is_gzip(header) && !is_zlib(header) {
+            return Ok(None);
+        }
+    }
+
+    let bytes = std::fs::read(path).map_err(|e| EngineError::Preprocess(e.to_string()))?;
+
+    if is_gzip(&bytes) {
+        return Ok(Some(inflate_gzip_with_dict(&bytes, preset_dict)?));
+    }
+    if is_zlib(&bytes) {
+        return Ok(Some(inflate_zlib_with_dict(&bytes, preset_dict)?));
+    }
+
+    Err(EngineError::Preprocess(format!(
+        "file {:?} has a compressed extension but no recognized zlib/gzip header",
+        path
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bit-level inverse of `BitReader`: bits are packed LSB-first within
+    /// each byte, same as `read_bit`/`read_bits` expect.
+    struct BitWriter {
+        bytes: Vec<u8>,
+        cur: u8,
+        bit_pos: u32,
+    }
+
+    impl BitWriter {
+        fn new() -> Self {
+            Self {
+                bytes: Vec::new(),
+                cur: 0,
+                bit_pos: 0,
+            }
+        }
+
+        fn write_bit(&mut self, bit: u32) {
+            self.cur |= ((bit & 1) as u8) << self.bit_pos;
+            self.bit_pos += 1;
+            if self.bit_pos == 8 {
+                self.bytes.push(self.cur);
+                self.cur = 0;
+                self.bit_pos = 0;
+            }
+        }
+
+        /// Packs `n` bits of `value`, least-significant bit first; matches
+        /// `BitReader::read_bits` (used for BFINAL/BTYPE and length/distance
+        /// extra bits).
+        fn write_bits_lsb_first(&mut self, value: u32, n: u32) {
+            for i in 0..n {
+                self.write_bit((value >> i) & 1);
+            }
+        }
+
+        /// Packs a `len`-bit canonical Huffman `code`, most-significant bit
+        /// first; matches `HuffmanTree::decode`, which shifts each newly
+        /// read bit into the low end of a code it compares MSB-first.
+        fn write_huffman_code(&mut self, code: u32, len: u32) {
+            for i in (0..len).rev() {
+                self.write_bit((code >> i) & 1);
+            }
+        }
+
+        fn align_to_byte(&mut self) {
+            while self.bit_pos != 0 {
+                self.write_bit(0);
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            self.align_to_byte();
+            self.bytes
+        }
+    }
+
+    /// Builds a single-block raw deflate stream (BFINAL=1) using an
+    /// uncompressed "stored" block, the simplest block type to hand-encode.
+    fn stored_block(payload: &[u8]) -> Vec<u8> {
+        let mut w = BitWriter::new();
+        w.write_bits_lsb_first(1, 1); // BFINAL
+        w.write_bits_lsb_first(0, 2); // BTYPE = stored
+        w.align_to_byte();
+        let mut bytes = w.finish();
+        let len = payload.len() as u16;
+        bytes.extend_from_slice(&len.to_le_bytes());
+        bytes.extend_from_slice(&(!len).to_le_bytes());
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    /// Fixed-Huffman code for deflate literal/length alphabet symbol `sym`,
+    /// per RFC 1951 §3.2.6 (the codes `fixed_huffman_trees` implicitly
+    /// assumes but never has to spell out, since its canonical decode table
+    /// is built straight from the lengths).
+    fn fixed_lit_code(sym: u32) -> (u32, u32) {
+        match sym {
+            0..=143 => (0x30 + sym, 8),
+            144..=255 => (0x190 + (sym - 144), 9),
+            256..=279 => (sym - 256, 7),
+            280..=287 => (0xc0 + (sym - 280), 8),
+            _ => panic!("symbol out of range"),
+        }
+    }
+
+    /// One block: a literal byte, then a length/distance back-reference,
+    /// then end-of-block (symbol 256), encoded with the fixed Huffman
+    /// tables. Used to exercise the length/distance decode path, which a
+    /// stored block can't reach.
+    fn fixed_huffman_block_with_backref(literal: u8, length: u16, distance: u16) -> Vec<u8> {
+        let len_idx = LENGTH_BASE
+            .iter()
+            .position(|&base| base == length)
+            .expect("test helper only supports exact LENGTH_BASE values");
+        let len_extra = LENGTH_EXTRA[len_idx] as u32;
+        let dist_idx = DIST_BASE
+            .iter()
+            .position(|&base| base == distance)
+            .expect("test helper only supports exact DIST_BASE values");
+        let dist_extra = DIST_EXTRA[dist_idx] as u32;
+
+        let mut w = BitWriter::new();
+        w.write_bits_lsb_first(1, 1); // BFINAL
+        w.write_bits_lsb_first(1, 2); // BTYPE = fixed Huffman
+
+        let (lit_code, lit_len) = fixed_lit_code(literal as u32);
+        w.write_huffman_code(lit_code, lit_len);
+
+        let (len_code, len_len) = fixed_lit_code(257 + len_idx as u32);
+        w.write_huffman_code(len_code, len_len);
+        w.write_bits_lsb_first(0, len_extra);
+
+        // Fixed distance codes are all 5 bits, assigned in symbol order.
+        w.write_huffman_code(dist_idx as u32, 5);
+        w.write_bits_lsb_first(0, dist_extra);
+
+        let (eob_code, eob_len) = fixed_lit_code(256);
+        w.write_huffman_code(eob_code, eob_len);
+
+        w.finish()
+    }
+
+    #[test]
+    fn test_inflate_raw_stored_block_round_trips() {
+        let payload = b"the quick brown fox";
+        let compressed = stored_block(payload);
+        assert_eq!(inflate_raw(&compressed).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_inflate_zlib_round_trips() {
+        let payload = b"zlib round trip payload";
+        let mut stream = vec![0x78, 0x9c];
+        stream.extend(stored_block(payload));
+        stream.extend_from_slice(&[0, 0, 0, 0]); // Adler-32 trailer, not checked.
+        assert_eq!(inflate_zlib(&stream).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_inflate_gzip_round_trips() {
+        let payload = b"gzip round trip payload";
+        let mut stream = vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+        stream.extend(stored_block(payload));
+        stream.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]); // CRC32 + ISIZE, not checked.
+        assert_eq!(inflate_gzip(&stream).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_inflate_raw_with_dict_back_reference_reaches_into_dictionary() {
+        // Dictionary is "hello ", the compressed stream itself holds a single
+        // literal 'X' followed by a length/distance pair that copies those 6
+        // bytes straight out of the seeded window, the same way zlib's
+        // `inflateSetDictionary` preset dictionaries work.
+        let dict = b"hello ";
+        let compressed = fixed_huffman_block_with_backref(b'X', 6, 7);
+
+        let out = inflate_raw_with_dict(&compressed, Some(dict)).unwrap();
+        assert_eq!(out, b"Xhello ");
+
+        // Without the dictionary the same distance points before the start
+        // of the stream, which is the bounds check this feature depends on.
+        assert!(inflate_raw_with_dict(&compressed, None).is_err());
+    }
+}
@@ -11,6 +11,16 @@
 //! - **Cross-Platform**: Linux, macOS, Windows, Android, iOS support
 //! - **Memory Safe**: Leverages Rust's safety guarantees
 //!
+//! ## Logging
+//!
+//! Internal diagnostics (per-box accept/reject decisions, recognition
+//! timings, and similar) are emitted through the [`log`] facade
+//! (`log::debug!`/`log::trace!`) rather than printed directly. This crate
+//! installs no logging backend itself, so embedders who want to see those
+//! diagnostics must install one of their own (e.g. `env_logger::init()`)
+//! before calling into `rusto` — the bundled `rusto` CLI binary does this
+//! for you behind its `-v`/`-vv`/`-vvv` flags.
+//!
 //! ## Quick Start
 //!
 //! ```rust,no_run
@@ -20,9 +30,10 @@
 //!     det_model_path: "models/det.onnx".to_string(),
 //!     rec_model_path: "models/rec.onnx".to_string(),
 //!     dict_path: "models/dict.txt".to_string(),
+//!     ..RapidOCRConfig::default()
 //! };
 //!
-//! let ocr = RapidOCR::new(config)?;
+//! let mut ocr = RapidOCR::new(config)?;
 //! let results = ocr.ocr("image.jpg")?;
 //!
 //! for result in results {
@@ -43,36 +54,62 @@ mod rapid_ocr;
 mod cal_rec_boxes;
 mod types;
 mod cls;
+mod inflate;
+mod visualize;
+
+#[cfg(not(feature = "use-opencv"))]
+mod rdp;
 
 #[cfg(not(feature = "use-opencv"))]
 mod contours;
 
+#[cfg(not(feature = "use-opencv"))]
+mod marching_squares;
+
+#[cfg(not(feature = "use-opencv"))]
+mod image_decode;
+
 // FFI module for C bindings
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
 // Public API exports
-pub use crate::rapid_ocr::RapidOcr;
+pub use crate::geometry::Quad;
+pub use crate::rapid_ocr::{OcrTimings, RapidOcr, RapidOcrOutput};
 pub use crate::types::{DetConfig, GlobalConfig, RecConfig};
+pub use crate::visualize::{BoxVisualizer, Color};
 
 // Re-export for easier access
 use crate::engine::EngineError;
 use std::path::Path;
 
+#[cfg(feature = "use-opencv")]
+use opencv::core::Point2f;
+
+#[cfg(not(feature = "use-opencv"))]
+use crate::image_impl::Point2f;
+
 /// Configuration for RapidOCR
 #[derive(Debug, Clone)]
 pub struct RapidOCRConfig {
     pub det_model_path: String,
     pub rec_model_path: String,
     pub dict_path: String,
+    /// Worker count for `ocr_batch`. Defaults to the detected core count.
+    pub num_threads: usize,
 }
 
 impl Default for RapidOCRConfig {
     fn default() -> Self {
+        let num_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+
         Self {
             det_model_path: String::new(),
             rec_model_path: String::new(),
             dict_path: String::new(),
+            num_threads,
         }
     }
 }
@@ -86,6 +123,17 @@ pub struct TextResult {
     pub box_points: [(f32, f32); 4],
 }
 
+/// A detected text-region box with its detection confidence, returned by
+/// `RapidOCR::detect` before recognition has run. Feed it back into
+/// `RapidOCR::recognize` to get text for the same regions without
+/// re-running detection.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DetectedBox {
+    pub score: f32,
+    /// Box points: [top-left, top-right, bottom-right, bottom-left]
+    pub box_points: [(f32, f32); 4],
+}
+
 /// Main RapidOCR interface
 pub struct RapidOCR {
     inner: RapidOcr,
@@ -94,53 +142,294 @@ pub struct RapidOCR {
 impl RapidOCR {
     /// Create a new RapidOCR instance
     pub fn new(config: RapidOCRConfig) -> Result<Self, EngineError> {
-        let inner = RapidOcr::new_ppv5(
+        let mut inner = RapidOcr::new_ppv5(
             &config.det_model_path,
             &config.rec_model_path,
             &config.dict_path,
         )?;
+        inner.global.num_threads = config.num_threads;
 
         Ok(Self { inner })
     }
 
     /// Run OCR on an image file
-    pub fn ocr<P: AsRef<Path>>(&self, image_path: P) -> Result<Vec<TextResult>, EngineError> {
+    pub fn ocr<P: AsRef<Path>>(&mut self, image_path: P) -> Result<Vec<TextResult>, EngineError> {
         let results = self.inner.run(image_path)?;
-        
-        // Convert RapidOcrOutput to Vec<TextResult>
-        Ok(results.boxes.into_iter()
-            .zip(results.txts.into_iter().zip(results.scores.into_iter()))
-            .map(|(boxes, (text, score))| TextResult {
-                text,
-                score,
+        Ok(rapid_ocr_output_to_text_results(results))
+    }
+
+    /// Run OCR like `ocr`, but return the full `RapidOcrOutput` instead of
+    /// flattening it to `TextResult`s. Needed by callers (e.g. the `--format
+    /// hocr`/`--format alto` CLI output) that want per-word boxes from
+    /// `word_results` rather than just one box per line.
+    pub fn ocr_detailed<P: AsRef<Path>>(&mut self, image_path: P) -> Result<RapidOcrOutput, EngineError> {
+        self.inner.run(image_path)
+    }
+
+    /// Run OCR on many image files, distributing them across
+    /// `RapidOCRConfig::num_threads` worker threads (see
+    /// `RapidOcr::run_batch`). Returns one `Result` per input path, in the
+    /// same order, so a failure on one image doesn't discard the rest.
+    pub fn ocr_batch<P: AsRef<Path> + Sync>(
+        &mut self,
+        image_paths: &[P],
+    ) -> Vec<Result<Vec<TextResult>, EngineError>> {
+        self.inner
+            .run_batch(image_paths)
+            .into_iter()
+            .map(|r| r.map(rapid_ocr_output_to_text_results))
+            .collect()
+    }
+
+    /// Run OCR on many image files like `ocr_batch`, but sharing this
+    /// instance's det/rec/cls sessions across every image (see
+    /// `RapidOcr::run_batch_mats`) instead of cloning a pipeline per worker
+    /// thread. Every image's detection crops are grouped into one batched
+    /// recognition call, amortizing the per-call tensor resize in
+    /// `MnnSession::run` across the whole folder instead of paying it once
+    /// per image. Fails the whole call if any image can't be decoded.
+    pub fn ocr_batch_shared<P: AsRef<Path>>(
+        &mut self,
+        image_paths: &[P],
+    ) -> Result<Vec<Vec<TextResult>>, EngineError> {
+        use crate::image_impl::imread;
+        let imgs = image_paths
+            .iter()
+            .map(imread)
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = self.inner.run_batch_mats(&imgs)?;
+        Ok(outputs
+            .into_iter()
+            .map(rapid_ocr_output_to_text_results)
+            .collect())
+    }
+
+    /// Run only the detection stage, returning text-region boxes and their
+    /// detection confidence without running recognition. Lets callers who
+    /// only need layout (or who want to feed boxes to a different
+    /// recognizer) skip the recognition cost entirely.
+    pub fn detect<P: AsRef<Path>>(&mut self, image_path: P) -> Result<Vec<DetectedBox>, EngineError> {
+        let boxes = self.inner.detect(image_path)?;
+        Ok(boxes
+            .into_iter()
+            .map(|b| DetectedBox {
+                score: b.score,
                 box_points: [
-                    (boxes[0].x, boxes[0].y),
-                    (boxes[1].x, boxes[1].y),
-                    (boxes[2].x, boxes[2].y),
-                    (boxes[3].x, boxes[3].y),
+                    (b.quad[0].x, b.quad[0].y),
+                    (b.quad[1].x, b.quad[1].y),
+                    (b.quad[2].x, b.quad[2].y),
+                    (b.quad[3].x, b.quad[3].y),
                 ],
-            }).collect())
-    }
-
-    /// Run OCR on image data in memory
-    pub fn ocr_from_bytes(&self, image_data: &[u8]) -> Result<Vec<TextResult>, EngineError> {
-        // Load image from bytes using image crate
-        use image::ImageReader;
-        use std::io::Cursor;
-        
-        let img = ImageReader::new(Cursor::new(image_data))
-            .with_guessed_format()
-            .map_err(|e| EngineError::ImageError(e.to_string()))?
-            .decode()
+            })
+            .collect())
+    }
+
+    /// Run recognition over boxes already returned by `detect`, such as when
+    /// the caller wants to re-detect once and re-recognize with different
+    /// settings, or filter boxes before paying the recognition cost.
+    pub fn recognize<P: AsRef<Path>>(
+        &mut self,
+        image_path: P,
+        boxes: &[DetectedBox],
+    ) -> Result<Vec<TextResult>, EngineError> {
+        use crate::image_impl::imread;
+        let img = imread(image_path)?;
+        let inner_boxes: Vec<crate::rapid_ocr::DetectedBox> = boxes
+            .iter()
+            .map(|b| crate::rapid_ocr::DetectedBox {
+                quad: Quad::new([
+                    Point2f::new(b.box_points[0].0, b.box_points[0].1),
+                    Point2f::new(b.box_points[1].0, b.box_points[1].1),
+                    Point2f::new(b.box_points[2].0, b.box_points[2].1),
+                    Point2f::new(b.box_points[3].0, b.box_points[3].1),
+                ]),
+                score: b.score,
+            })
+            .collect();
+
+        let (txts, scores) = self.inner.recognize(&img, &inner_boxes)?;
+        Ok(txts
+            .into_iter()
+            .zip(scores)
+            .zip(boxes.iter())
+            .map(|((text, score), b)| TextResult {
+                text,
+                score,
+                box_points: b.box_points,
+            })
+            .collect())
+    }
+
+    /// Run OCR like `ocr`, but also return a per-stage timing breakdown
+    /// (preprocess, detection, crop/rectify, recognition, postprocess), so
+    /// callers can see where time goes instead of only a single duration.
+    pub fn ocr_timed<P: AsRef<Path>>(
+        &mut self,
+        image_path: P,
+    ) -> Result<(Vec<TextResult>, OcrTimings), EngineError> {
+        let (results, timings) = self.inner.run_timed(image_path)?;
+        Ok((rapid_ocr_output_to_text_results(results), timings))
+    }
+
+    /// Run OCR on every page of a multi-page input (TIFF/PDF). Single-page
+    /// formats, including HEIF/HEIC and camera RAW (gated behind the `heif`
+    /// and `raw` features respectively), return one element. Pages are
+    /// numbered from 0 in source order.
+    #[cfg(not(feature = "use-opencv"))]
+    pub fn ocr_pages<P: AsRef<Path>>(
+        &mut self,
+        image_path: P,
+    ) -> Result<Vec<(usize, Vec<TextResult>)>, EngineError> {
+        let pages = crate::image_decode::decode_pages(image_path.as_ref())?;
+        pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, img)| {
+                let mat = crate::image_impl::Mat::new(img);
+                let results = self.inner.run_on_mat(&mat)?;
+                Ok((i, rapid_ocr_output_to_text_results(results)))
+            })
+            .collect()
+    }
+
+    /// Run OCR on an already-decoded in-memory image, with no disk round-trip.
+    #[cfg(not(feature = "use-opencv"))]
+    pub fn ocr_image(&mut self, img: &image::DynamicImage) -> Result<Vec<TextResult>, EngineError> {
+        let mat = crate::image_impl::Mat::new(img.clone());
+        let results = self.inner.run_on_mat(&mat)?;
+        Ok(rapid_ocr_output_to_text_results(results))
+    }
+
+    /// Run OCR on an already-decoded in-memory image, with no disk round-trip.
+    #[cfg(feature = "use-opencv")]
+    pub fn ocr_image(&mut self, img: &image::DynamicImage) -> Result<Vec<TextResult>, EngineError> {
+        let mat = crate::image_impl::mat_from_dynamic(img)
             .map_err(|e| EngineError::ImageError(e.to_string()))?;
-        
-        // Save to temp file and process
-        let temp_path = std::env::temp_dir().join(format!("rapidocr_{}.jpg", std::process::id()));
-        img.save(&temp_path)
+        let results = self.inner.run_on_mat(&mat)?;
+        Ok(rapid_ocr_output_to_text_results(results))
+    }
+
+    /// Decode and run OCR on an encoded image held in memory (e.g. bytes read
+    /// from a request body), with no disk round-trip.
+    #[cfg(not(feature = "use-opencv"))]
+    pub fn ocr_bytes(&mut self, image_data: &[u8]) -> Result<Vec<TextResult>, EngineError> {
+        let img = image::load_from_memory(image_data).map_err(|e| EngineError::ImageError(e.to_string()))?;
+        self.ocr_image(&img)
+    }
+
+    /// Decode and run OCR on an encoded image held in memory (e.g. bytes read
+    /// from a request body), with no disk round-trip.
+    #[cfg(feature = "use-opencv")]
+    pub fn ocr_bytes(&mut self, image_data: &[u8]) -> Result<Vec<TextResult>, EngineError> {
+        let mat = crate::image_impl::imdecode_bytes(image_data)
             .map_err(|e| EngineError::ImageError(e.to_string()))?;
-        
-        let result = self.ocr(&temp_path);
-        let _ = std::fs::remove_file(&temp_path);
-        result
+        let results = self.inner.run_on_mat(&mat)?;
+        Ok(rapid_ocr_output_to_text_results(results))
+    }
+
+    /// Run OCR on an already-decoded CHW (channel, height, width) `u8` pixel
+    /// tensor, such as one produced by another `ndarray`-based pipeline stage
+    /// without ever materializing an `image::DynamicImage`. `chw` must have 3
+    /// channels in RGB order. Converts to HWC internally (the layout `image`
+    /// and `Mat` both use) and flows through `ocr_image`, which copies the
+    /// transposed image once more into a `Mat`; there is no disk round-trip.
+    pub fn ocr_ndarray(
+        &mut self,
+        chw: ndarray::ArrayView3<u8>,
+    ) -> Result<Vec<TextResult>, EngineError> {
+        let (channels, height, width) = chw.dim();
+        if channels != 3 {
+            return Err(EngineError::ImageError(format!(
+                "ocr_ndarray expects 3 RGB channels, got {channels}"
+            )));
+        }
+
+        let mut hwc = image::RgbImage::new(width as u32, height as u32);
+        for y in 0..height {
+            for x in 0..width {
+                hwc.put_pixel(
+                    x as u32,
+                    y as u32,
+                    image::Rgb([chw[[0, y, x]], chw[[1, y, x]], chw[[2, y, x]]]),
+                );
+            }
+        }
+
+        self.ocr_image(&image::DynamicImage::ImageRgb8(hwc))
+    }
+
+    /// Run OCR on image data in memory. Kept for compatibility with existing
+    /// callers (see `ffi::rocr_ocr_data`); prefer `ocr_bytes`.
+    pub fn ocr_from_bytes(&mut self, image_data: &[u8]) -> Result<Vec<TextResult>, EngineError> {
+        self.ocr_bytes(image_data)
     }
 }
+
+/// Render OCR results as machine-readable JSON: a `regions` array with one
+/// `{ text, score, box }` object per result, plus a summary (region count,
+/// mean score), so results can be piped into other services without each
+/// caller reimplementing the shape. This is not the shape of `main.rs`'s
+/// `--format json` output, which lists `boxes`, `txts` and `scores` as
+/// parallel arrays.
+pub fn to_json(results: &[TextResult]) -> serde_json::Value {
+    let mean_score = if results.is_empty() {
+        0.0
+    } else {
+        results.iter().map(|r| r.score).sum::<f32>() / results.len() as f32
+    };
+
+    serde_json::json!({
+        "regions": results.iter().map(|r| serde_json::json!({
+            "text": r.text,
+            "score": r.score,
+            "box": r.box_points.iter().map(|&(x, y)| [x, y]).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+        "summary": {
+            "region_count": results.len(),
+            "mean_score": mean_score,
+        },
+    })
+}
+
+/// Exposes the pure-Rust `warp_perspective` for `benches/warp_perspective_benchmark.rs`.
+/// Not part of the supported public API — only `image_impl`'s internal
+/// callers (`geometry`'s quad cropping) should use the real thing.
+#[doc(hidden)]
+#[cfg(not(feature = "use-opencv"))]
+pub fn __bench_warp_perspective(
+    src: &image::DynamicImage,
+    matrix: &[[f64; 3]; 3],
+    width: i32,
+    height: i32,
+) -> image::DynamicImage {
+    let mat = crate::image_impl::Mat::new(src.clone());
+    let mut dst = crate::image_impl::Mat::default();
+    crate::image_impl::warp_perspective(
+        &mat,
+        &mut dst,
+        matrix,
+        crate::image_impl::Size::new(width, height),
+        0,
+        0,
+    )
+    .expect("warp_perspective failed");
+    dst.as_dynamic().clone()
+}
+
+fn rapid_ocr_output_to_text_results(results: crate::rapid_ocr::RapidOcrOutput) -> Vec<TextResult> {
+    results
+        .boxes
+        .into_iter()
+        .zip(results.txts.into_iter().zip(results.scores.into_iter()))
+        .map(|(boxes, (text, score))| TextResult {
+            text,
+            score,
+            box_points: [
+                (boxes[0].x, boxes[0].y),
+                (boxes[1].x, boxes[1].y),
+                (boxes[2].x, boxes[2].y),
+                (boxes[3].x, boxes[3].y),
+            ],
+        })
+        .collect()
+}
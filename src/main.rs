@@ -1,5 +1,5 @@
 use clap::{Parser, ValueEnum};
-use rusto::{RapidOCR, RapidOCRConfig};
+use rusto::{RapidOCR, RapidOCRConfig, RapidOcrOutput};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -24,6 +24,14 @@ struct Cli {
     /// Output format
     #[arg(short, long, value_enum, default_value_t = OutputFormat::Json)]
     format: OutputFormat,
+
+    /// Increase diagnostic verbosity: `-v` for info, `-vv` for debug, `-vvv`
+    /// for trace (per-box accept/reject decisions from
+    /// `rapid_ocr::run_on_mat`). The library logs through the `log` crate
+    /// but installs no logger itself, so without this flag (or a logger an embedder installs
+    /// before calling into `rusto`) those diagnostics are silently dropped.
+    #[arg(short = 'v', long, action = clap::ArgAction::Count)]
+    verbose: u8,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -34,60 +42,217 @@ enum OutputFormat {
     Text,
     /// TSV format: text\tscore\tx1,y1,x2,y2,x3,y3,x4,y4
     Tsv,
+    /// hOCR (XHTML with ocr_page/ocrx_word elements), for document pipelines
+    /// that expect the standard hOCR interchange format
+    Hocr,
+    /// ALTO XML (TextBlock/TextLine/String elements), for document pipelines
+    /// that expect the standard ALTO interchange format
+    Alto,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
+    // `rusto` logs its internal diagnostics (the score filter's box count
+    // and box accept/reject decisions) through the `log` facade and installs no
+    // backend of its own. Install one here so `-v`/`-vv`/`-vvv` restores the
+    // visibility `eprintln!` used to give unconditionally; without this flag
+    // we stay quiet by default rather than spamming warnings on every run.
+    let level = match cli.verbose {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new().filter_level(level).init();
+
     // Initialize OCR
     let config = RapidOCRConfig {
         det_model_path: cli.det_model.to_str().unwrap().to_string(),
         rec_model_path: cli.rec_model.to_str().unwrap().to_string(),
         dict_path: cli.dict.to_str().unwrap().to_string(),
+        ..RapidOCRConfig::default()
     };
 
-    let ocr = RapidOCR::new(config)?;
+    let mut ocr = RapidOCR::new(config)?;
 
     // Load image
     let image_path = cli.image.to_str().unwrap();
-    
+
     // Run OCR
-    let results = ocr.ocr(image_path)?;
+    let results = ocr.ocr_detailed(image_path)?;
 
     // Output results
     match cli.format {
         OutputFormat::Json => {
             let json_output = serde_json::json!({
-                "boxes": results.iter().map(|r| vec![
-                    serde_json::json!({"x": r.box_points[0].0, "y": r.box_points[0].1}),
-                    serde_json::json!({"x": r.box_points[1].0, "y": r.box_points[1].1}),
-                    serde_json::json!({"x": r.box_points[2].0, "y": r.box_points[2].1}),
-                    serde_json::json!({"x": r.box_points[3].0, "y": r.box_points[3].1}),
+                "boxes": results.boxes.iter().map(|b| vec![
+                    serde_json::json!({"x": b[0].x, "y": b[0].y}),
+                    serde_json::json!({"x": b[1].x, "y": b[1].y}),
+                    serde_json::json!({"x": b[2].x, "y": b[2].y}),
+                    serde_json::json!({"x": b[3].x, "y": b[3].y}),
                 ]).collect::<Vec<_>>(),
-                "txts": results.iter().map(|r| &r.text).collect::<Vec<_>>(),
-                "scores": results.iter().map(|r| r.score).collect::<Vec<_>>(),
-                "word_results": results.iter().map(|_| Vec::<String>::new()).collect::<Vec<_>>(),
+                "txts": &results.txts,
+                "scores": &results.scores,
+                "word_results": results.word_results.iter().map(|words| {
+                    words.iter().map(|(text, _, _)| text.clone()).collect::<Vec<_>>()
+                }).collect::<Vec<_>>(),
             });
             println!("{}", serde_json::to_string_pretty(&json_output)?);
         }
         OutputFormat::Text => {
-            for result in &results {
-                println!("{}", result.text);
+            for text in &results.txts {
+                println!("{}", text);
             }
         }
         OutputFormat::Tsv => {
-            for result in &results {
+            for ((text, score), b) in results.txts.iter().zip(&results.scores).zip(&results.boxes) {
                 let box_str = format!(
                     "{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1},{:.1}",
-                    result.box_points[0].0, result.box_points[0].1,
-                    result.box_points[1].0, result.box_points[1].1,
-                    result.box_points[2].0, result.box_points[2].1,
-                    result.box_points[3].0, result.box_points[3].1,
+                    b[0].x, b[0].y, b[1].x, b[1].y, b[2].x, b[2].y, b[3].x, b[3].y,
                 );
-                println!("{}\t{:.3}\t{}", result.text, result.score, box_str);
+                println!("{}\t{:.3}\t{}", text, score, box_str);
             }
         }
+        OutputFormat::Hocr => {
+            println!("{}", render_hocr(&results));
+        }
+        OutputFormat::Alto => {
+            println!("{}", render_alto(&results));
+        }
     }
 
     Ok(())
 }
+
+/// Axis-aligned `(x1, y1, x2, y2)` bounds of an arbitrary quad, given as an
+/// iterator of `(x, y)` point coordinates. Takes plain coordinate pairs
+/// rather than a concrete point type so it works for both `Quad`'s boxes and
+/// `word_results`' per-word quads without naming either point type.
+fn axis_aligned_bounds(points: impl Iterator<Item = (f32, f32)>) -> (i32, i32, i32, i32) {
+    let mut x1 = f32::INFINITY;
+    let mut y1 = f32::INFINITY;
+    let mut x2 = f32::NEG_INFINITY;
+    let mut y2 = f32::NEG_INFINITY;
+    for (x, y) in points {
+        x1 = x1.min(x);
+        y1 = y1.min(y);
+        x2 = x2.max(x);
+        y2 = y2.max(y);
+    }
+    (x1.round() as i32, y1.round() as i32, x2.round() as i32, y2.round() as i32)
+}
+
+/// Escape the handful of characters that aren't valid verbatim inside XML
+/// text content or a quoted attribute value.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Render `results` as hOCR: one `ocr_page` div holding one `ocrx_word` span
+/// per recognized word (or per line, when `word_results` wasn't populated),
+/// with `bbox x1 y1 x2 y2` and `x_wconf` confidence title attributes.
+fn render_hocr(results: &RapidOcrOutput) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Transitional//EN\" \"http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd\">\n");
+    out.push_str("<html xmlns=\"http://www.w3.org/1999/xhtml\">\n<head>\n<title></title>\n");
+    out.push_str("<meta http-equiv=\"Content-Type\" content=\"text/html; charset=utf-8\"/>\n");
+    out.push_str("<meta name=\"ocr-system\" content=\"rusto\"/>\n");
+    out.push_str("<meta name=\"ocr-capabilities\" content=\"ocr_page ocrx_word\"/>\n");
+    out.push_str("</head>\n<body>\n");
+    out.push_str("<div class=\"ocr_page\" id=\"page_1\">\n");
+
+    let mut word_id = 0usize;
+    for (idx, b) in results.boxes.iter().enumerate() {
+        let words = &results.word_results[idx];
+        if !words.is_empty() {
+            for (text, score, quad) in words {
+                let (x1, y1, x2, y2) = axis_aligned_bounds(quad.iter().map(|p| (p.x, p.y)));
+                word_id += 1;
+                let _ = writeln_ocrx_word(&mut out, word_id, x1, y1, x2, y2, *score, text);
+            }
+        } else {
+            let (x1, y1, x2, y2) = axis_aligned_bounds(b.iter().map(|p| (p.x, p.y)));
+            word_id += 1;
+            let _ = writeln_ocrx_word(&mut out, word_id, x1, y1, x2, y2, results.scores[idx], &results.txts[idx]);
+        }
+    }
+
+    out.push_str("</div>\n</body>\n</html>\n");
+    out
+}
+
+fn writeln_ocrx_word(
+    out: &mut String,
+    word_id: usize,
+    x1: i32,
+    y1: i32,
+    x2: i32,
+    y2: i32,
+    score: f32,
+    text: &str,
+) -> std::fmt::Result {
+    use std::fmt::Write;
+    writeln!(
+        out,
+        "<span class=\"ocrx_word\" id=\"word_1_{}\" title=\"bbox {} {} {} {}; x_wconf {}\">{}</span>",
+        word_id,
+        x1,
+        y1,
+        x2,
+        y2,
+        (score * 100.0).round() as i32,
+        escape_xml(text),
+    )
+}
+
+/// Render `results` as ALTO XML: one `TextBlock` holding one `TextLine` per
+/// detected box, each with `HPOS`/`VPOS`/`WIDTH`/`HEIGHT` from its
+/// axis-aligned bounds and one `String` child per recognized word (or per
+/// line, when `word_results` wasn't populated).
+fn render_alto(results: &RapidOcrOutput) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<alto xmlns=\"http://www.loc.gov/standards/alto/ns-v4#\">\n");
+    out.push_str("  <Layout>\n    <Page ID=\"page_1\">\n      <PrintSpace>\n");
+    out.push_str("        <TextBlock ID=\"block_1\">\n");
+
+    for (idx, b) in results.boxes.iter().enumerate() {
+        let (x1, y1, x2, y2) = axis_aligned_bounds(b.iter().map(|p| (p.x, p.y)));
+        let _ = writeln!(
+            out,
+            "          <TextLine ID=\"line_{}\" HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\">",
+            idx + 1, x1, y1, x2 - x1, y2 - y1,
+        );
+
+        let words = &results.word_results[idx];
+        if !words.is_empty() {
+            for (word_idx, (text, score, quad)) in words.iter().enumerate() {
+                let (wx1, wy1, wx2, wy2) = axis_aligned_bounds(quad.iter().map(|p| (p.x, p.y)));
+                let _ = writeln!(
+                    out,
+                    "            <String ID=\"string_{}_{}\" CONTENT=\"{}\" HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\" WC=\"{:.3}\"/>",
+                    idx + 1, word_idx + 1, escape_xml(text), wx1, wy1, wx2 - wx1, wy2 - wy1, score,
+                );
+            }
+        } else {
+            let _ = writeln!(
+                out,
+                "            <String ID=\"string_{}_1\" CONTENT=\"{}\" HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\" WC=\"{:.3}\"/>",
+                idx + 1, escape_xml(&results.txts[idx]), x1, y1, x2 - x1, y2 - y1, results.scores[idx],
+            );
+        }
+
+        out.push_str("          </TextLine>\n");
+    }
+
+    out.push_str("        </TextBlock>\n      </PrintSpace>\n    </Page>\n  </Layout>\n</alto>\n");
+    out
+}
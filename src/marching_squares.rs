@@ -0,0 +1,295 @@
+//! Sub-pixel isoline extraction from a scalar field via marching squares.
+//!
+//! `contours::find_contours` only sees a hard-thresholded 0/255 mask, so a
+//! detected box's edges snap to whole pixels. `marching_squares` instead
+//! walks the detector's raw float probability map cell by cell and places
+//! each boundary crossing by linear interpolation, giving fractional-pixel
+//! contours that `postprocess`'s min-area-rect/box-from-polygon logic can
+//! fit a tighter quad to, particularly useful for small glyphs where a
+//! single pixel of slop is a large relative error.
+
+/// One sub-pixel boundary segment, in the same `(x, y)` grid coordinates as
+/// `prob`'s `[row][col]` indices (fractional where the isoline crosses a
+/// cell edge).
+type Point = (f32, f32);
+type Segment = (Point, Point);
+
+/// Which of a 2x2 cell's four edges (top, right, bottom, left) the isoline
+/// crosses, linearly interpolated between the two corner values straddling
+/// `level`. Corners are indexed `tl, tr, br, bl`; returns `None` for an edge
+/// whose corners are on the same side of `level`.
+struct CellCrossings {
+    top: Option<Point>,
+    right: Option<Point>,
+    bottom: Option<Point>,
+    left: Option<Point>,
+}
+
+/// Point on the edge from `a_pt` (value `a`) to `b_pt` (value `b`) where the
+/// field crosses `level`, at fraction `(level - a) / (b - a)` along the edge.
+/// Falls back to the edge midpoint when `a` and `b` are too close to
+/// distinguish, rather than dividing by (near) zero.
+fn lerp_point(a_pt: Point, a: f32, b_pt: Point, b: f32, level: f32) -> Point {
+    let denom = b - a;
+    let t = if denom.abs() < 1e-9 {
+        0.5
+    } else {
+        ((level - a) / denom).clamp(0.0, 1.0)
+    };
+    (a_pt.0 + (b_pt.0 - a_pt.0) * t, a_pt.1 + (b_pt.1 - a_pt.1) * t)
+}
+
+/// Extract this cell's crossing points and, when the cell is a "saddle"
+/// (corners above `level` are diagonal, e.g. top-left and bottom-right but
+/// not their neighbors), pair them into the two segments that are
+/// consistent with the cell's average value — the usual "asymptotic
+/// decider" simplification: if the center exceeds `level`, the above-level
+/// corners are treated as connected through the middle, otherwise the
+/// below-level ones are.
+fn cell_segments(
+    x: usize,
+    y: usize,
+    tl: f32,
+    tr: f32,
+    br: f32,
+    bl: f32,
+    level: f32,
+) -> Vec<Segment> {
+    let (xf, yf) = (x as f32, y as f32);
+    let (p_tl, p_tr, p_br, p_bl) = ((xf, yf), (xf + 1.0, yf), (xf + 1.0, yf + 1.0), (xf, yf + 1.0));
+
+    let crossings = CellCrossings {
+        top: ((tl > level) != (tr > level)).then(|| lerp_point(p_tl, tl, p_tr, tr, level)),
+        right: ((tr > level) != (br > level)).then(|| lerp_point(p_tr, tr, p_br, br, level)),
+        bottom: ((br > level) != (bl > level)).then(|| lerp_point(p_br, br, p_bl, bl, level)),
+        left: ((bl > level) != (tl > level)).then(|| lerp_point(p_bl, bl, p_tl, tl, level)),
+    };
+
+    let above = [tl > level, tr > level, br > level, bl > level];
+    let case =
+        (above[0] as u8) | ((above[1] as u8) << 1) | ((above[2] as u8) << 2) | ((above[3] as u8) << 3);
+
+    match case {
+        0 | 15 => Vec::new(),
+        1 | 14 => vec![(crossings.left.unwrap(), crossings.top.unwrap())],
+        2 | 13 => vec![(crossings.top.unwrap(), crossings.right.unwrap())],
+        3 | 12 => vec![(crossings.left.unwrap(), crossings.right.unwrap())],
+        4 | 11 => vec![(crossings.right.unwrap(), crossings.bottom.unwrap())],
+        6 | 9 => vec![(crossings.top.unwrap(), crossings.bottom.unwrap())],
+        7 | 8 => vec![(crossings.left.unwrap(), crossings.bottom.unwrap())],
+        5 => {
+            // Saddle: tl & br above, tr & bl below.
+            let center = (tl + tr + br + bl) / 4.0;
+            if center > level {
+                vec![
+                    (crossings.top.unwrap(), crossings.right.unwrap()),
+                    (crossings.left.unwrap(), crossings.bottom.unwrap()),
+                ]
+            } else {
+                vec![
+                    (crossings.left.unwrap(), crossings.top.unwrap()),
+                    (crossings.right.unwrap(), crossings.bottom.unwrap()),
+                ]
+            }
+        }
+        10 => {
+            // Saddle: tr & bl above, tl & br below.
+            let center = (tl + tr + br + bl) / 4.0;
+            if center > level {
+                vec![
+                    (crossings.left.unwrap(), crossings.top.unwrap()),
+                    (crossings.right.unwrap(), crossings.bottom.unwrap()),
+                ]
+            } else {
+                vec![
+                    (crossings.top.unwrap(), crossings.right.unwrap()),
+                    (crossings.left.unwrap(), crossings.bottom.unwrap()),
+                ]
+            }
+        }
+        _ => unreachable!("case is a 4-bit index, 0..16"),
+    }
+}
+
+/// Quantize a point to a hashable key for endpoint matching, snapping to
+/// 1/1024 of a grid cell so segments that share a boundary crossing (computed
+/// twice, once per adjacent cell) land on the same key despite float
+/// rounding.
+fn endpoint_key(p: Point) -> (i64, i64) {
+    const SCALE: f32 = 1024.0;
+    ((p.0 * SCALE).round() as i64, (p.1 * SCALE).round() as i64)
+}
+
+/// Chain unordered `segments` into closed (or, failing that, open) polylines
+/// by repeatedly following each segment to the next one sharing an endpoint.
+fn chain_segments(segments: Vec<Segment>) -> Vec<Vec<Point>> {
+    use std::collections::HashMap;
+
+    let mut by_endpoint: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (i, &(a, b)) in segments.iter().enumerate() {
+        by_endpoint.entry(endpoint_key(a)).or_default().push(i);
+        by_endpoint.entry(endpoint_key(b)).or_default().push(i);
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut polylines = Vec::new();
+
+    for start in 0..segments.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let (a, b) = segments[start];
+        let mut polyline = vec![a, b];
+        let mut current_end = b;
+
+        loop {
+            let key = endpoint_key(current_end);
+            let next = by_endpoint
+                .get(&key)
+                .into_iter()
+                .flatten()
+                .copied()
+                .find(|&i| !used[i]);
+
+            let Some(next) = next else { break };
+            used[next] = true;
+            let (na, nb) = segments[next];
+            let other_end = if endpoint_key(na) == key { nb } else { na };
+            polyline.push(other_end);
+            current_end = other_end;
+
+            if endpoint_key(current_end) == endpoint_key(polyline[0]) {
+                break;
+            }
+        }
+
+        polylines.push(polyline);
+    }
+
+    polylines
+}
+
+/// Extract sub-pixel isolines of `prob` at `level` via marching squares:
+/// each 2x2 block of cells forms a 4-bit case from which corners exceed
+/// `level`, and the crossed edges are linearly interpolated and chained into
+/// polylines. `prob` is indexed `prob[row][col]` (y then x), matching the
+/// detector's `(height, width)` probability map layout. `level` plays the
+/// same role as `DbPostProcess::thresh`'s hard cutoff, but sub-pixel instead
+/// of snapping to whole pixels.
+pub fn marching_squares(prob: &[Vec<f32>], level: f32) -> Vec<Vec<(f32, f32)>> {
+    if prob.len() < 2 || prob[0].len() < 2 {
+        return Vec::new();
+    }
+
+    let (height, width) = (prob.len(), prob[0].len());
+    let mut segments = Vec::new();
+
+    for y in 0..height - 1 {
+        for x in 0..width - 1 {
+            let (tl, tr) = (prob[y][x], prob[y][x + 1]);
+            let (bl, br) = (prob[y + 1][x], prob[y + 1][x + 1]);
+            segments.extend(cell_segments(x, y, tl, tr, br, bl, level));
+        }
+    }
+
+    chain_segments(segments)
+}
+
+/// Bilinearly upsample `prob` by integer factor `precision` (e.g. `2` doubles
+/// both dimensions), then run `marching_squares`, dividing the result back
+/// down into the original grid's coordinate space. Smooths the traced
+/// isolines the way supersampling smooths rasterized curves in vector art,
+/// at the cost of `precision^2` more cells to visit. `precision <= 1` is
+/// equivalent to calling `marching_squares` directly.
+pub fn marching_squares_supersampled(
+    prob: &[Vec<f32>],
+    level: f32,
+    precision: usize,
+) -> Vec<Vec<(f32, f32)>> {
+    if precision <= 1 {
+        return marching_squares(prob, level);
+    }
+
+    let (height, width) = (prob.len(), prob[0].len());
+    let (fine_h, fine_w) = ((height - 1) * precision + 1, (width - 1) * precision + 1);
+    let scale = precision as f32;
+
+    let mut fine = vec![vec![0.0f32; fine_w]; fine_h];
+    for (fy, row) in fine.iter_mut().enumerate() {
+        let y = fy as f32 / scale;
+        let y0 = (y.floor() as usize).min(height - 2);
+        let fy_frac = y - y0 as f32;
+
+        for (fx, cell) in row.iter_mut().enumerate() {
+            let x = fx as f32 / scale;
+            let x0 = (x.floor() as usize).min(width - 2);
+            let fx_frac = x - x0 as f32;
+
+            let top = prob[y0][x0] * (1.0 - fx_frac) + prob[y0][x0 + 1] * fx_frac;
+            let bottom = prob[y0 + 1][x0] * (1.0 - fx_frac) + prob[y0 + 1][x0 + 1] * fx_frac;
+            *cell = top * (1.0 - fy_frac) + bottom * fy_frac;
+        }
+    }
+
+    marching_squares(&fine, level)
+        .into_iter()
+        .map(|polyline| polyline.into_iter().map(|(x, y)| (x / scale, y / scale)).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marching_squares_disk_is_closed_loop() {
+        // A soft circular blob: center near 1.0, falling off toward the edges.
+        let n = 20;
+        let mut prob = vec![vec![0.0f32; n]; n];
+        let (cx, cy) = (n as f32 / 2.0, n as f32 / 2.0);
+        for y in 0..n {
+            for x in 0..n {
+                let d = ((x as f32 - cx).powi(2) + (y as f32 - cy).powi(2)).sqrt();
+                prob[y][x] = (1.0 - d / (n as f32 / 2.0)).max(0.0);
+            }
+        }
+
+        let polylines = marching_squares(&prob, 0.5);
+        assert_eq!(polylines.len(), 1, "a single blob should trace one loop");
+        let polyline = &polylines[0];
+        assert!(polyline.len() > 8);
+
+        // Closed: first and last point coincide.
+        let (first, last) = (polyline[0], *polyline.last().unwrap());
+        assert!((first.0 - last.0).abs() < 1e-3 && (first.1 - last.1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_marching_squares_sub_pixel_crossing() {
+        // A single cell, step function crossing exactly 1/4 of the way
+        // across the top edge: tl=0.0, tr=1.0, level=0.25 -> x=0.25.
+        let prob = vec![vec![0.0, 1.0, 1.0], vec![0.0, 1.0, 1.0], vec![0.0, 1.0, 1.0]];
+        let polylines = marching_squares(&prob, 0.25);
+        assert!(!polylines.is_empty());
+        let has_quarter_crossing = polylines
+            .iter()
+            .flatten()
+            .any(|&(x, _)| (x - 0.25).abs() < 1e-4);
+        assert!(has_quarter_crossing);
+    }
+
+    #[test]
+    fn test_marching_squares_supersampled_matches_unscaled_at_precision_one() {
+        let prob = vec![vec![0.0, 1.0], vec![0.0, 1.0]];
+        let base = marching_squares(&prob, 0.5);
+        let same = marching_squares_supersampled(&prob, 0.5, 1);
+        assert_eq!(base.len(), same.len());
+    }
+
+    #[test]
+    fn test_marching_squares_empty_on_tiny_input() {
+        let prob = vec![vec![1.0]];
+        assert!(marching_squares(&prob, 0.5).is_empty());
+    }
+}
@@ -5,10 +5,10 @@ use opencv::{core, imgproc, prelude::*};
 use ndarray::Array4;
 
 #[cfg(feature = "use-opencv")]
-use opencv::core::Mat;
+use opencv::core::{Mat, Point2f};
 
 #[cfg(not(feature = "use-opencv"))]
-use crate::image_impl::Mat;
+use crate::image_impl::{Mat, Point2f};
 
 #[cfg(feature = "use-opencv")]
 use geo_clipper::{Clipper, EndType, JoinType};
@@ -16,20 +16,18 @@ use geo_clipper::{Clipper, EndType, JoinType};
 use geo_types::{Coord, LineString, Polygon};
 
 use crate::engine::EngineError;
+use crate::geometry::Quad;
 
-#[cfg(feature = "use-opencv")]
-pub struct TextDetOutput {
-    pub img: Option<Mat>,
-    pub boxes: Option<Vec<[core::Point2f; 4]>>,
-    pub scores: Option<Vec<f32>>,
-    pub elapse: f64,
-}
-
-#[cfg(not(feature = "use-opencv"))]
 pub struct TextDetOutput {
     pub img: Option<Mat>,
-    pub boxes: Option<Vec<[crate::image_impl::Point2f; 4]>>,
+    pub boxes: Option<Vec<Quad>>,
     pub scores: Option<Vec<f32>>,
+    /// Full polygon for each box, populated only when `DetConfig.box_type`
+    /// is `"poly"`. `boxes` still carries the min-area-rect quad fitted to
+    /// each region, so existing crop/recognition code keeps working
+    /// unchanged; `polys` is the curved outline for callers (e.g.
+    /// visualization) that want the true contour instead.
+    pub polys: Option<Vec<Vec<Point2f>>>,
     pub elapse: f64,
 }
 
@@ -39,26 +37,17 @@ impl TextDetOutput {
             img: None,
             boxes: None,
             scores: None,
+            polys: None,
             elapse: 0.0,
         }
     }
 
-    #[cfg(feature = "use-opencv")]
-    pub fn new(img: Mat, boxes: Vec<[core::Point2f; 4]>, scores: Vec<f32>, elapse: f64) -> Self {
-        Self {
-            img: Some(img),
-            boxes: Some(boxes),
-            scores: Some(scores),
-            elapse,
-        }
-    }
-
-    #[cfg(not(feature = "use-opencv"))]
-    pub fn new(img: Mat, boxes: Vec<[crate::image_impl::Point2f; 4]>, scores: Vec<f32>, elapse: f64) -> Self {
+    pub fn new(img: Mat, boxes: Vec<Quad>, scores: Vec<f32>, elapse: f64) -> Self {
         Self {
             img: Some(img),
             boxes: Some(boxes),
             scores: Some(scores),
+            polys: None,
             elapse,
         }
     }
@@ -68,6 +57,13 @@ impl TextDetOutput {
     }
 }
 
+/// DB (Differentiable Binarization) detection post-processing.
+///
+/// Turns the detector's per-pixel probability map into text boxes: threshold
+/// into a binary mask, trace contours, fit a box per contour, score it
+/// against the probability map (`score_mode`), then expand it outward by
+/// `unclip_ratio` so the box covers the full glyph stroke rather than just
+/// the shrunk region the model was trained to predict.
 pub struct DBPostProcess {
     pub thresh: f32,
     pub box_thresh: f32,
@@ -75,6 +71,19 @@ pub struct DBPostProcess {
     pub unclip_ratio: f64,
     pub min_size: f32,
     pub use_dilation: bool,
+    pub score_mode: String,
+    pub box_type: String,
+    /// Trace boundaries with `marching_squares` against the raw probability
+    /// map instead of `find_contours` against a hard-thresholded mask, so
+    /// boxes gain fractional-pixel precision. Pure-Rust-backend only (the
+    /// OpenCV path's `findContours` has no sub-pixel equivalent plumbed in,
+    /// so this field is accepted but unused there); see
+    /// `DetConfig.sub_pixel_contours`.
+    pub sub_pixel_contours: bool,
+    /// Supersampling factor `marching_squares_supersampled` uses when
+    /// `sub_pixel_contours` is set; `1` disables supersampling. See
+    /// `DetConfig.contour_precision`.
+    pub contour_precision: usize,
 }
 
 #[cfg(feature = "use-opencv")]
@@ -93,9 +102,37 @@ impl DBPostProcess {
             unclip_ratio: unclip_ratio as f64,
             min_size: 3.0,
             use_dilation,
+            score_mode: "fast".to_string(),
+            box_type: "quad".to_string(),
+            sub_pixel_contours: false,
+            contour_precision: 1,
         }
     }
 
+    /// Select between the cheap quad-mask score ("fast") and the more
+    /// accurate raw-contour score ("slow"), matching `DetConfig.score_mode`.
+    pub fn with_score_mode(mut self, score_mode: String) -> Self {
+        self.score_mode = score_mode;
+        self
+    }
+
+    /// Select between the default min-area-rect `"quad"` output and
+    /// `"poly"`, which keeps the full detected outline for curved or
+    /// irregular text. Call `process_poly` instead of `process` to get the
+    /// polygon once this is set to `"poly"`, matching `DetConfig.box_type`.
+    pub fn with_box_type(mut self, box_type: String) -> Self {
+        self.box_type = box_type;
+        self
+    }
+
+    /// No-op on the OpenCV backend (see `sub_pixel_contours`'s doc comment);
+    /// kept so callers can chain it regardless of which backend is compiled.
+    pub fn with_sub_pixel_contours(mut self, enabled: bool, precision: usize) -> Self {
+        self.sub_pixel_contours = enabled;
+        self.contour_precision = precision.max(1);
+        self
+    }
+
     pub fn process(
         &self,
         pred: &Array4<f32>,
@@ -107,6 +144,42 @@ impl DBPostProcess {
             return Ok((Vec::new(), Vec::new()));
         }
 
+        let contours = self.contours_from_pred(pred, h, w)?;
+        let (boxes, scores) = self.boxes_from_bitmap(pred, &contours, w, h, ori_w, ori_h)?;
+        let (boxes, scores) = self.filter_det_res(boxes, scores, ori_h, ori_w);
+
+        Ok((boxes, scores))
+    }
+
+    /// Polygon variant of `process`: keeps the full (possibly >4-point)
+    /// outline traced from the probability map instead of fitting each
+    /// region to a `Quad`, for curved or irregular text where a straight
+    /// box would crop away part of the glyph. Used when `box_type` is
+    /// `"poly"`.
+    pub fn process_poly(
+        &self,
+        pred: &Array4<f32>,
+        ori_h: i32,
+        ori_w: i32,
+    ) -> Result<(Vec<Vec<Point2f>>, Vec<f32>), EngineError> {
+        let (_, _, h, w) = pred.dim();
+        if h == 0 || w == 0 {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let contours = self.contours_from_pred(pred, h, w)?;
+        self.polygons_from_bitmap(pred, &contours, w, h, ori_w, ori_h)
+    }
+
+    /// Threshold `pred` into a binary mask (with optional dilation) and
+    /// trace its contours, shared by both the quad (`process`) and polygon
+    /// (`process_poly`) paths.
+    fn contours_from_pred(
+        &self,
+        pred: &Array4<f32>,
+        h: usize,
+        w: usize,
+    ) -> Result<core::Vector<core::Vector<core::Point>>, EngineError> {
         // Build mask from prediction
         // pred is NCHW: (1, 1, height, width) where height=736, width=1184
         // OpenCV Mat is (rows, cols) where rows=height, cols=width
@@ -116,7 +189,7 @@ impl DBPostProcess {
             core::CV_8UC1,
             core::Scalar::all(0.0),
         )?;
-        
+
         // Fill mask row by row
         for y in 0..h {
             for x in 0..w {
@@ -125,7 +198,7 @@ impl DBPostProcess {
                 *mask_mat.at_2d_mut::<u8>(y as i32, x as i32)? = val;
             }
         }
-        
+
         // Optional dilation, like Python's use_dilation with a 2x2 kernel of ones
         let mut dilated = Mat::default();
         let mut mask_for_contours: &Mat = &mask_mat;
@@ -151,11 +224,8 @@ impl DBPostProcess {
             imgproc::CHAIN_APPROX_SIMPLE,
             core::Point::new(0, 0),
         )?;
-        
-        let (boxes, scores) = self.boxes_from_bitmap(pred, &contours, w, h, ori_w, ori_h)?;
-        let (boxes, scores) = self.filter_det_res(boxes, scores, ori_h, ori_w);
 
-        Ok((boxes, scores))
+        Ok(contours)
     }
 
     fn boxes_from_bitmap(
@@ -182,7 +252,11 @@ impl DBPostProcess {
                 continue;
             }
 
-            let score = self.box_score_fast(pred, &box_pts, height, width)?;
+            let score = if self.score_mode == "slow" {
+                self.box_score_slow(pred, &contour, height, width)?
+            } else {
+                self.box_score_fast(pred, &box_pts, height, width)?
+            };
             if score < self.box_thresh {
                 continue;
             }
@@ -211,6 +285,75 @@ impl DBPostProcess {
         Ok((boxes, scores))
     }
 
+    /// Polygon counterpart of `boxes_from_bitmap`: simplifies each contour
+    /// with `approxPolyDP` instead of fitting it to a min-area rectangle,
+    /// scores it against the raw contour, then unclips it while keeping
+    /// however many vertices the simplification produced.
+    fn polygons_from_bitmap(
+        &self,
+        pred: &Array4<f32>,
+        contours: &core::Vector<core::Vector<core::Point>>,
+        width: usize,
+        height: usize,
+        dest_width: i32,
+        dest_height: i32,
+    ) -> Result<(Vec<Vec<Point2f>>, Vec<f32>), EngineError> {
+        let num_contours = contours.len().min(self.max_candidates);
+        let mut polys = Vec::new();
+        let mut scores = Vec::new();
+
+        for i in 0..num_contours {
+            let contour = contours.get(i)?;
+            if contour.len() < 3 {
+                continue;
+            }
+
+            let epsilon = 0.002 * imgproc::arc_length(&contour, true)?;
+            let mut approx = core::Vector::<core::Point>::new();
+            imgproc::approx_poly_dp(&contour, &mut approx, epsilon, true)?;
+            if approx.len() < 4 {
+                continue;
+            }
+
+            let score = self.box_score_slow(pred, &approx, height, width)?;
+            if score < self.box_thresh {
+                continue;
+            }
+
+            let approx_pts: Vec<core::Point2f> = approx
+                .iter()
+                .map(|p| core::Point2f::new(p.x as f32, p.y as f32))
+                .collect();
+            let unclipped = self.unclip_poly(&approx_pts)?;
+            if unclipped.len() < 4 {
+                continue;
+            }
+
+            let src_h = dest_height as f32;
+            let src_w = dest_width as f32;
+            let mut scaled = unclipped;
+            for p in &mut scaled {
+                p.x = (p.x / width as f32 * src_w).round();
+                p.y = (p.y / height as f32 * src_h).round();
+            }
+
+            // Clip against the image rect with Sutherland-Hodgman, same as
+            // the quad path's `clip_det_res`, instead of clamping each point
+            // independently: a point-wise clamp would slide a vertex that
+            // lies outside the frame along a diagonal, so the polygon no
+            // longer meets the border where its edge actually crosses it.
+            let clipped = clip_polygon_to_rect(&scaled, 0.0, 0.0, src_w - 1.0, src_h - 1.0);
+            if clipped.len() < 3 {
+                continue;
+            }
+
+            polys.push(clipped);
+            scores.push(score);
+        }
+
+        Ok((polys, scores))
+    }
+
     fn get_mini_box(
         &self,
         contour: &core::Vector<core::Point>,
@@ -350,6 +493,82 @@ impl DBPostProcess {
         }
     }
 
+    /// Polygon-accurate score: rasterize the *raw* contour (not its
+    /// min-area rectangle) and average only the probability-map pixels it
+    /// covers. Slower than `box_score_fast` but noticeably more accurate
+    /// for tilted or dense text, since it excludes background pixels the
+    /// bounding rectangle would otherwise include.
+    fn box_score_slow(
+        &self,
+        pred: &Array4<f32>,
+        contour: &core::Vector<core::Point>,
+        h: usize,
+        w: usize,
+    ) -> Result<f32, EngineError> {
+        let mut xmin = i32::MAX;
+        let mut xmax = i32::MIN;
+        let mut ymin = i32::MAX;
+        let mut ymax = i32::MIN;
+        for pt in contour.iter() {
+            xmin = xmin.min(pt.x);
+            xmax = xmax.max(pt.x);
+            ymin = ymin.min(pt.y);
+            ymax = ymax.max(pt.y);
+        }
+
+        xmin = xmin.max(0).min(w as i32 - 1);
+        xmax = xmax.max(0).min(w as i32 - 1);
+        ymin = ymin.max(0).min(h as i32 - 1);
+        ymax = ymax.max(0).min(h as i32 - 1);
+
+        if xmax <= xmin || ymax <= ymin {
+            return Ok(0.0);
+        }
+
+        let mask_h = ymax - ymin + 1;
+        let mask_w = xmax - xmin + 1;
+
+        let mut mask = Mat::zeros(mask_h, mask_w, core::CV_8UC1)?.to_mat()?;
+        let shifted: core::Vector<core::Point> = contour
+            .iter()
+            .map(|p| core::Point::new(p.x - xmin, p.y - ymin))
+            .collect();
+        let pts_vec = core::Vector::<core::Vector<core::Point>>::from(vec![shifted]);
+
+        imgproc::fill_poly(
+            &mut mask,
+            &pts_vec,
+            core::Scalar::all(1.0),
+            imgproc::LINE_8,
+            0,
+            core::Point::new(0, 0),
+        )?;
+
+        let mut sum = 0.0f32;
+        let mut count = 0usize;
+        for yy in ymin..=ymax {
+            for xx in xmin..=xmax {
+                let mask_val = mask.at_2d::<u8>(yy - ymin, xx - xmin)?;
+                if *mask_val > 0 {
+                    sum += pred[[0, 0, yy as usize, xx as usize]];
+                    count += 1;
+                }
+            }
+        }
+
+        if count == 0 {
+            Ok(0.0)
+        } else {
+            Ok(sum / count as f32)
+        }
+    }
+
+    /// Grow the shrunken DB-predicted quad back out to the true text extent
+    /// by offsetting each edge outward by `distance = area * unclip_ratio /
+    /// perimeter` (shoelace area, summed edge length), mirroring PaddleOCR's
+    /// `unclip`. `unclip_ratio` is the tunable passed into `DBPostProcess::new`;
+    /// see `unclip_poly` for the arbitrary-length-polygon generalization used
+    /// by the `"poly"` box-type path.
     fn unclip(&self, box_pts: &[core::Point2f; 4]) -> Result<Vec<core::Point2f>, EngineError> {
         // Compute polygon area and perimeter (shoelace formula + edge lengths), as in Python.
         let mut area = 0.0f64;
@@ -410,6 +629,67 @@ impl DBPostProcess {
         Ok(result)
     }
 
+    /// Same offset-expansion as `unclip`, generalized to an arbitrary-length
+    /// polygon instead of a fixed 4-point box, for the `"poly"` box-type path.
+    fn unclip_poly(&self, pts: &[core::Point2f]) -> Result<Vec<core::Point2f>, EngineError> {
+        let n = pts.len();
+        if n < 3 {
+            return Ok(pts.to_vec());
+        }
+
+        let mut area = 0.0f64;
+        let mut length = 0.0f64;
+        for i in 0..n {
+            let p1 = pts[i];
+            let p2 = pts[(i + 1) % n];
+            area += (p1.x as f64) * (p2.y as f64) - (p2.x as f64) * (p1.y as f64);
+            let dx = p1.x as f64 - p2.x as f64;
+            let dy = p1.y as f64 - p2.y as f64;
+            length += (dx * dx + dy * dy).sqrt();
+        }
+        area = (area * 0.5).abs();
+        if area <= 0.0 || length <= 0.0 {
+            return Ok(pts.to_vec());
+        }
+
+        let distance = area * self.unclip_ratio / length;
+
+        let coords: Vec<Coord<f64>> = pts
+            .iter()
+            .map(|p| Coord { x: p.x as f64, y: p.y as f64 })
+            .collect();
+
+        let mut ring = coords.clone();
+        if let Some(first) = coords.first() {
+            if coords.last().map(|c| c.x != first.x || c.y != first.y).unwrap_or(false) {
+                ring.push(*first);
+            }
+        }
+
+        let poly = Polygon::new(LineString::from(ring), vec![]);
+
+        let mpoly = poly.offset(distance, JoinType::Round(1.0), EndType::ClosedPolygon, 1.0f64);
+        let first_poly = match mpoly.0.first() {
+            Some(p) => p,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut result = Vec::new();
+        for coord in first_poly.exterior().0.iter() {
+            result.push(core::Point2f::new(coord.x as f32, coord.y as f32));
+        }
+
+        if result.len() > 1 {
+            let first = result[0];
+            let last = result[result.len() - 1];
+            if (first.x - last.x).abs() < 0.01 && (first.y - last.y).abs() < 0.01 {
+                result.pop();
+            }
+        }
+
+        Ok(result)
+    }
+
     fn filter_det_res(
         &self,
         dt_boxes: Vec<[core::Point2f; 4]>,
@@ -438,6 +718,20 @@ impl DBPostProcess {
                 continue;
             }
 
+            // Robust box NMS: DB's contour-per-region detection can yield
+            // two overlapping quads for the same text line; drop this one if
+            // every one of its corners already lies inside (or right on the
+            // edge of) a box we've already accepted, rather than relying on
+            // a coarser IoU/area heuristic.
+            let is_duplicate = dt_boxes_new.iter().any(|accepted: &[core::Point2f; 4]| {
+                box_pts
+                    .iter()
+                    .all(|p| point_polygon_distance(p.x, p.y, accepted) >= -1.0)
+            });
+            if is_duplicate {
+                continue;
+            }
+
             dt_boxes_new.push(box_pts);
             new_scores.push(score);
         }
@@ -467,17 +761,187 @@ impl DBPostProcess {
         [tl, tr, br, bl]
     }
 
+    /// Clip `points` to the image rectangle and re-fit the result to a
+    /// `Quad`. A box that exits the image on a diagonal edge is properly
+    /// cut at the boundary (Sutherland–Hodgman) rather than having each
+    /// corner clamped independently, which used to turn a valid rotated box
+    /// into a self-intersecting quadrilateral.
     fn clip_det_res(
         &self,
-        mut points: [core::Point2f; 4],
+        points: [core::Point2f; 4],
         img_height: i32,
         img_width: i32,
     ) -> [core::Point2f; 4] {
-        for p in &mut points {
-            p.x = p.x.max(0.0).min((img_width - 1) as f32);
-            p.y = p.y.max(0.0).min((img_height - 1) as f32);
+        let clamp_each = |mut pts: [core::Point2f; 4]| {
+            for p in &mut pts {
+                p.x = p.x.max(0.0).min((img_width - 1) as f32);
+                p.y = p.y.max(0.0).min((img_height - 1) as f32);
+            }
+            pts
+        };
+
+        let clipped = clip_polygon_to_rect(&points, 0.0, 0.0, (img_width - 1) as f32, (img_height - 1) as f32);
+        if clipped.len() < 3 {
+            return clamp_each(points);
+        }
+
+        match self.get_mini_box_points(&clipped) {
+            Ok((box_pts, _)) => box_pts,
+            Err(_) => clamp_each(points),
+        }
+    }
+}
+
+/// Sutherland–Hodgman clip of a (possibly non-convex) polygon against the
+/// axis-aligned rectangle `[min_x, max_x] x [min_y, max_y]`, clipping
+/// against each of the 4 boundary half-planes in turn.
+#[cfg(feature = "use-opencv")]
+fn clip_polygon_to_rect(
+    points: &[core::Point2f],
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+) -> Vec<core::Point2f> {
+    fn clip_edge(
+        poly: &[core::Point2f],
+        inside: impl Fn(core::Point2f) -> bool,
+        intersect: impl Fn(core::Point2f, core::Point2f) -> core::Point2f,
+    ) -> Vec<core::Point2f> {
+        if poly.is_empty() {
+            return Vec::new();
+        }
+        let n = poly.len();
+        let mut out = Vec::with_capacity(n + 1);
+        for i in 0..n {
+            let curr = poly[i];
+            let prev = poly[(i + n - 1) % n];
+            let curr_in = inside(curr);
+            let prev_in = inside(prev);
+            if curr_in {
+                if !prev_in {
+                    out.push(intersect(prev, curr));
+                }
+                out.push(curr);
+            } else if prev_in {
+                out.push(intersect(prev, curr));
+            }
+        }
+        out
+    }
+
+    fn lerp_x(p1: core::Point2f, p2: core::Point2f, x: f32) -> core::Point2f {
+        let t = if (p2.x - p1.x).abs() < 1e-9 { 0.0 } else { (x - p1.x) / (p2.x - p1.x) };
+        core::Point2f::new(x, p1.y + t * (p2.y - p1.y))
+    }
+
+    fn lerp_y(p1: core::Point2f, p2: core::Point2f, y: f32) -> core::Point2f {
+        let t = if (p2.y - p1.y).abs() < 1e-9 { 0.0 } else { (y - p1.y) / (p2.y - p1.y) };
+        core::Point2f::new(p1.x + t * (p2.x - p1.x), y)
+    }
+
+    let mut poly = points.to_vec();
+    poly = clip_edge(&poly, |p| p.x >= min_x, |p1, p2| lerp_x(p1, p2, min_x));
+    poly = clip_edge(&poly, |p| p.x <= max_x, |p1, p2| lerp_x(p1, p2, max_x));
+    poly = clip_edge(&poly, |p| p.y >= min_y, |p1, p2| lerp_y(p1, p2, min_y));
+    poly = clip_edge(&poly, |p| p.y <= max_y, |p1, p2| lerp_y(p1, p2, max_y));
+    poly
+}
+
+/// Signed distance from `(x, y)` to the boundary of `polygon`, matching
+/// OpenCV's `pointPolygonTest(..., measureDist=true)`: positive when the
+/// point is inside, negative when outside, zero on an edge. The magnitude is
+/// the minimum distance to any edge segment (projecting onto each segment
+/// and clamping to its endpoints); the sign comes from the same even-odd
+/// ray-casting rule used elsewhere in this module to decide inside/outside.
+/// Used by `filter_det_res` to drop a candidate box whose corners all fall
+/// inside one already accepted, a more robust duplicate check than a coarse
+/// IoU/area threshold.
+#[cfg(feature = "use-opencv")]
+fn point_polygon_distance(x: f32, y: f32, polygon: &[core::Point2f]) -> f32 {
+    let n = polygon.len();
+    if n == 0 {
+        return f32::NEG_INFINITY;
+    }
+
+    let mut min_dist = f32::MAX;
+    let mut inside = false;
+    let mut j = n - 1;
+
+    for i in 0..n {
+        let pi = polygon[i];
+        let pj = polygon[j];
+
+        let dx = pj.x - pi.x;
+        let dy = pj.y - pi.y;
+        let len_sq = dx * dx + dy * dy;
+        let t = if len_sq > 1e-12 {
+            (((x - pi.x) * dx + (y - pi.y) * dy) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let proj_x = pi.x + t * dx;
+        let proj_y = pi.y + t * dy;
+        let seg_dist = ((x - proj_x).powi(2) + (y - proj_y).powi(2)).sqrt();
+        min_dist = min_dist.min(seg_dist);
+
+        if (pi.y > y) != (pj.y > y) {
+            let x_at_y = pi.x + (y - pi.y) * dx / dy;
+            if x < x_at_y {
+                inside = !inside;
+            }
+        }
+
+        j = i;
+    }
+
+    if inside {
+        min_dist
+    } else {
+        -min_dist
+    }
+}
+
+#[cfg(all(test, feature = "use-opencv"))]
+mod opencv_clip_tests {
+    use super::*;
+
+    fn post() -> DBPostProcess {
+        DBPostProcess::new(0.3, 0.6, 1000, 1.5, false)
+    }
+
+    #[test]
+    fn test_clip_det_res_box_fully_inside_is_unchanged() {
+        let pts = [
+            core::Point2f::new(10.0, 10.0),
+            core::Point2f::new(20.0, 10.0),
+            core::Point2f::new(20.0, 20.0),
+            core::Point2f::new(10.0, 20.0),
+        ];
+        let clipped = post().clip_det_res(pts, 100, 100);
+        for (a, b) in pts.iter().zip(clipped.iter()) {
+            assert!((a.x - b.x).abs() < 1e-3);
+            assert!((a.y - b.y).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_clip_det_res_corner_outside_is_cut_not_sheared() {
+        // Mirrors the pure-Rust `clip_det_res_pure` test: a true
+        // Sutherland-Hodgman clip keeps every vertex within the image
+        // rectangle, whereas per-point clamping would shear the box by
+        // dragging the out-of-bounds corner straight to the nearest edge.
+        let pts = [
+            core::Point2f::new(5.0, 5.0),
+            core::Point2f::new(30.0, -10.0),
+            core::Point2f::new(30.0, 15.0),
+            core::Point2f::new(5.0, 15.0),
+        ];
+        let clipped = post().clip_det_res(pts, 20, 20);
+        for p in &clipped {
+            assert!(p.x >= -1e-3 && p.x <= 19.0 + 1e-3);
+            assert!(p.y >= -1e-3 && p.y <= 19.0 + 1e-3);
         }
-        points
     }
 }
 
@@ -498,9 +962,40 @@ impl DBPostProcess {
             unclip_ratio: unclip_ratio as f64,
             min_size: 3.0,
             use_dilation,
+            score_mode: "fast".to_string(),
+            box_type: "quad".to_string(),
+            sub_pixel_contours: false,
+            contour_precision: 1,
         }
     }
 
+    /// Select between the cheap quad-mask score ("fast") and the more
+    /// accurate raw-contour score ("slow"), matching `DetConfig.score_mode`.
+    pub fn with_score_mode(mut self, score_mode: String) -> Self {
+        self.score_mode = score_mode;
+        self
+    }
+
+    /// Select between the default min-area-rect `"quad"` output and
+    /// `"poly"`, which keeps the full detected outline for curved or
+    /// irregular text. Call `process_poly` instead of `process` to get the
+    /// polygon once this is set to `"poly"`, matching `DetConfig.box_type`.
+    pub fn with_box_type(mut self, box_type: String) -> Self {
+        self.box_type = box_type;
+        self
+    }
+
+    /// Trace contours from the raw probability map via
+    /// `marching_squares_supersampled` instead of `find_contours` against a
+    /// hard-thresholded mask, giving `process` fractional-pixel boundary
+    /// points (`precision` supersamples the map first for smoother curves;
+    /// `1` disables supersampling). Matches `DetConfig.sub_pixel_contours`.
+    pub fn with_sub_pixel_contours(mut self, enabled: bool, precision: usize) -> Self {
+        self.sub_pixel_contours = enabled;
+        self.contour_precision = precision.max(1);
+        self
+    }
+
     pub fn process(
         &self,
         pred: &ndarray::Array4<f32>,
@@ -509,59 +1004,80 @@ impl DBPostProcess {
     ) -> Result<(Vec<[crate::image_impl::Point2f; 4]>, Vec<f32>), EngineError> {
         use crate::contours::find_contours;
         use crate::image_impl::{Point2f, min_area_rect, box_points};
+        use crate::marching_squares::marching_squares_supersampled;
         use image::{GrayImage, Luma};
-        
+
         let (_, _, h, w) = pred.dim();
         if h == 0 || w == 0 {
             return Ok((Vec::new(), Vec::new()));
         }
 
-        // Create binary mask from prediction
-        let mut binary_img = GrayImage::new(w as u32, h as u32);
-        for y in 0..h {
-            for x in 0..w {
-                let val = pred[[0, 0, y, x]];
-                if val > self.thresh {
-                    binary_img.put_pixel(x as u32, y as u32, Luma([255]));
-                } else {
-                    binary_img.put_pixel(x as u32, y as u32, Luma([0]));
+        // Each entry is one candidate boundary's points, in probability-map
+        // (float) coordinates, sorted by (shoelace) area descending.
+        let mut contour_points: Vec<Vec<Point2f>> = if self.sub_pixel_contours {
+            let prob_grid: Vec<Vec<f32>> = (0..h)
+                .map(|y| (0..w).map(|x| pred[[0, 0, y, x]]).collect())
+                .collect();
+            let mut polylines: Vec<Vec<Point2f>> = marching_squares_supersampled(
+                &prob_grid,
+                self.thresh,
+                self.contour_precision,
+            )
+            .into_iter()
+            .map(|poly| poly.into_iter().map(|(x, y)| Point2f::new(x, y)).collect())
+            .collect();
+            polylines.sort_by(|a, b| {
+                let area_a = polygon_area_f32(a);
+                let area_b = polygon_area_f32(b);
+                area_b.partial_cmp(&area_a).unwrap_or(std::cmp::Ordering::Equal)
+            });
+            polylines
+        } else {
+            // Create binary mask from prediction
+            let mut binary_img = GrayImage::new(w as u32, h as u32);
+            for y in 0..h {
+                for x in 0..w {
+                    let val = pred[[0, 0, y, x]];
+                    if val > self.thresh {
+                        binary_img.put_pixel(x as u32, y as u32, Luma([255]));
+                    } else {
+                        binary_img.put_pixel(x as u32, y as u32, Luma([0]));
+                    }
                 }
             }
-        }
 
-        // Optionally dilate to connect nearby regions (2x2 kernel like OpenCV)
-        let img_for_contours = if self.use_dilation {
-            dilate_2x2(&binary_img)
-        } else {
-            binary_img.clone()
+            // Optionally dilate to connect nearby regions (2x2 kernel like OpenCV)
+            let img_for_contours = if self.use_dilation {
+                dilate_2x2(&binary_img)
+            } else {
+                binary_img.clone()
+            };
+
+            // Find contours
+            let mut contours = find_contours(&img_for_contours);
+
+            // Sort contours by area (descending) - largest first
+            contours.sort_by(|a, b| {
+                let area_a = calculate_contour_area(a);
+                let area_b = calculate_contour_area(b);
+                area_b.partial_cmp(&area_a).unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            contours
+                .into_iter()
+                .map(|c| c.points.into_iter().map(|(x, y)| Point2f::new(x as f32, y as f32)).collect())
+                .collect()
         };
 
-        // Find contours
-        let mut contours = find_contours(&img_for_contours);
-        
-        // Sort contours by area (descending) - largest first
-        contours.sort_by(|a, b| {
-            let area_a = calculate_contour_area(a);
-            let area_b = calculate_contour_area(b);
-            area_b.partial_cmp(&area_a).unwrap_or(std::cmp::Ordering::Equal)
-        });
-        
-        let num_contours = contours.len().min(self.max_candidates);
+        let num_contours = contour_points.len().min(self.max_candidates);
         let mut boxes = Vec::new();
         let mut scores = Vec::new();
 
-        for contour in contours.iter().take(num_contours) {
-            if contour.len() < 4 {
+        for points in contour_points.drain(..num_contours) {
+            if points.len() < 4 {
                 continue;
             }
 
-            // Convert contour points to f32
-            let points: Vec<Point2f> = contour
-                .points
-                .iter()
-                .map(|&(x, y)| Point2f::new(x as f32, y as f32))
-                .collect();
-
             // Get minimum area rectangle
             let (center, size, angle) = min_area_rect(&points)?;
             let rect_points = box_points(center, size, angle);
@@ -572,7 +1088,11 @@ impl DBPostProcess {
             }
 
             // Calculate score for this box
-            let score = self.box_score_fast_pure(pred, &rect_points, h, w)?;
+            let score = if self.score_mode == "slow" {
+                self.box_score_slow_pure(pred, &points, h, w)?
+            } else {
+                self.box_score_fast_pure(pred, &rect_points, h, w)?
+            };
             if score < self.box_thresh {
                 continue;
             }
@@ -633,13 +1153,136 @@ impl DBPostProcess {
                 continue;
             }
 
+            // Robust box NMS: DB's contour-per-region detection can yield
+            // two overlapping quads for the same text line; drop this one if
+            // every one of its corners already lies inside (or right on the
+            // edge of) a box we've already accepted, rather than relying on
+            // a coarser IoU/area heuristic.
+            let is_duplicate = boxes.iter().any(|accepted: &[Point2f; 4]| {
+                final_box
+                    .iter()
+                    .all(|p| point_polygon_distance(p.x, p.y, accepted) >= -1.0)
+            });
+            if is_duplicate {
+                continue;
+            }
+
             boxes.push(final_box);
             scores.push(score);
         }
-        
+
         Ok((boxes, scores))
     }
 
+    /// Polygon variant of `process`: keeps the traced contour instead of
+    /// fitting it to a min-area rectangle, for curved or irregular text.
+    /// Used when `box_type` is `"poly"`. Like the OpenCV backend, this first
+    /// simplifies each contour with Douglas-Peucker (`approx_poly_dp`, with
+    /// epsilon at 0.2% of the contour's perimeter).
+    pub fn process_poly(
+        &self,
+        pred: &ndarray::Array4<f32>,
+        ori_h: i32,
+        ori_w: i32,
+    ) -> Result<(Vec<Vec<crate::image_impl::Point2f>>, Vec<f32>), EngineError> {
+        use crate::contours::find_contours;
+        use crate::image_impl::Point2f;
+        use image::{GrayImage, Luma};
+
+        let (_, _, h, w) = pred.dim();
+        if h == 0 || w == 0 {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let mut binary_img = GrayImage::new(w as u32, h as u32);
+        for y in 0..h {
+            for x in 0..w {
+                let val = pred[[0, 0, y, x]];
+                if val > self.thresh {
+                    binary_img.put_pixel(x as u32, y as u32, Luma([255]));
+                } else {
+                    binary_img.put_pixel(x as u32, y as u32, Luma([0]));
+                }
+            }
+        }
+
+        let img_for_contours = if self.use_dilation {
+            dilate_2x2(&binary_img)
+        } else {
+            binary_img.clone()
+        };
+
+        let mut contours = find_contours(&img_for_contours);
+        contours.sort_by(|a, b| {
+            let area_a = calculate_contour_area(a);
+            let area_b = calculate_contour_area(b);
+            area_b.partial_cmp(&area_a).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let num_contours = contours.len().min(self.max_candidates);
+        let mut polys = Vec::new();
+        let mut scores = Vec::new();
+
+        for contour in contours.iter().take(num_contours) {
+            if contour.len() < 4 {
+                continue;
+            }
+
+            let perimeter = contour_perimeter(contour);
+            let points = approx_poly_dp(contour, 0.002 * perimeter, true);
+            if points.len() < 4 || signed_polygon_area(&points).abs() < 1.0 {
+                continue;
+            }
+
+            let score = self.box_score_slow_pure(pred, &points, h, w)?;
+            if score < self.box_thresh {
+                continue;
+            }
+
+            let unclipped = self.unclip_poly_pure(&points)?;
+            if unclipped.len() < 4 {
+                continue;
+            }
+
+            let scaled: Vec<Point2f> = unclipped
+                .into_iter()
+                .map(|p| {
+                    Point2f::new(
+                        p.x * (ori_w as f32 / w as f32),
+                        p.y * (ori_h as f32 / h as f32),
+                    )
+                })
+                .collect();
+
+            // Clip against the image rect with Sutherland-Hodgman, same as
+            // the quad path's `clip_det_res_pure`, instead of clamping each
+            // point independently: a point-wise clamp would slide a vertex
+            // that lies outside the frame along a diagonal, so the polygon
+            // no longer meets the border where its edge actually crosses it.
+            let clipped = clip_polygon_to_rect(
+                &scaled,
+                0.0,
+                0.0,
+                (ori_w - 1) as f32,
+                (ori_h - 1) as f32,
+            );
+            if clipped.len() < 3 {
+                continue;
+            }
+
+            polys.push(clipped);
+            scores.push(score);
+        }
+
+        Ok((polys, scores))
+    }
+
+    /// Mean of `pred` over the quad, via a scanline sweep instead of a
+    /// per-pixel `point_in_polygon` test: each row's intersections with the
+    /// 4 edges are computed once and the pixels between each pair are
+    /// summed directly, so cost scales with the box's pixel area rather
+    /// than area × edges. Matters on large detections, where the per-pixel
+    /// test used to re-walk all 4 edges for every pixel in the bbox.
     fn box_score_fast_pure(
         &self,
         pred: &ndarray::Array4<f32>,
@@ -662,16 +1305,87 @@ impl DBPostProcess {
             return Ok(0.0);
         }
 
-        // Compute mean score inside polygon using point-in-polygon test
         let mut sum = 0.0f32;
-        let mut count = 0;
+        let mut count = 0usize;
+
+        for y in ymin..=ymax {
+            let scan_y = y as f32 + 0.5;
+            let mut xs = scanline_intersections(scan_y, box_pts);
+            if xs.len() < 2 {
+                continue;
+            }
+            xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+            let mut pair = 0;
+            while pair + 1 < xs.len() {
+                let x_start = ((xs[pair] - 0.5).ceil() as i32).max(xmin);
+                let x_end = ((xs[pair + 1] - 0.5).floor() as i32).min(xmax);
+                for x in x_start..=x_end {
+                    sum += pred[[0, 0, y as usize, x as usize]];
+                    count += 1;
+                }
+                pair += 2;
+            }
+        }
+
+        if count == 0 {
+            Ok(0.0)
+        } else {
+            Ok(sum / count as f32)
+        }
+    }
+
+    /// High-accuracy score: test against the raw (possibly concave) traced
+    /// contour instead of its fitted min-area rectangle, so a tilted or
+    /// dense box is scored against the glyph's exact outline rather than a
+    /// looser rectangle that would pull in background pixels. Uses the same
+    /// scanline sweep as `box_score_fast_pure` so the exactness doesn't cost
+    /// an extra point-in-polygon test per pixel.
+    fn box_score_slow_pure(
+        &self,
+        pred: &ndarray::Array4<f32>,
+        contour: &[crate::image_impl::Point2f],
+        h: usize,
+        w: usize,
+    ) -> Result<f32, EngineError> {
+        if contour.len() < 3 {
+            return Ok(0.0);
+        }
+
+        let xmin = contour.iter().map(|p| p.x).fold(f32::INFINITY, f32::min).floor() as i32;
+        let xmax = contour.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max).ceil() as i32;
+        let ymin = contour.iter().map(|p| p.y).fold(f32::INFINITY, f32::min).floor() as i32;
+        let ymax = contour.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max).ceil() as i32;
+
+        let xmin = xmin.max(0).min(w as i32 - 1);
+        let xmax = xmax.max(0).min(w as i32 - 1);
+        let ymin = ymin.max(0).min(h as i32 - 1);
+        let ymax = ymax.max(0).min(h as i32 - 1);
+
+        if xmin >= xmax || ymin >= ymax {
+            return Ok(0.0);
+        }
+
+        let mut sum = 0.0f32;
+        let mut count = 0usize;
 
         for y in ymin..=ymax {
-            for x in xmin..=xmax {
-                if point_in_polygon(x as f32 + 0.5, y as f32 + 0.5, box_pts) {
+            let scan_y = y as f32 + 0.5;
+            let mut xs = scanline_intersections(scan_y, contour);
+            if xs.len() < 2 {
+                continue;
+            }
+            xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+            let mut pair = 0;
+            while pair + 1 < xs.len() {
+                let x_start = ((xs[pair] - 0.5).ceil() as i32).max(xmin);
+                let x_end = ((xs[pair + 1] - 0.5).floor() as i32).min(xmax);
+                for x in x_start..=x_end {
                     sum += pred[[0, 0, y as usize, x as usize]];
                     count += 1;
                 }
+                pair += 2;
             }
         }
 
@@ -682,6 +1396,11 @@ impl DBPostProcess {
         }
     }
 
+    /// Pure-Rust counterpart to `unclip`: grows the shrunken DB-predicted
+    /// quad back out by `distance = area * unclip_ratio / perimeter`, where
+    /// `unclip_ratio` is the tunable passed into `DBPostProcess::new`. See
+    /// `unclip_poly_pure` for the arbitrary-length-polygon generalization
+    /// used by the `"poly"` box-type path.
     fn unclip_pure(&self, box_pts: &[crate::image_impl::Point2f; 4]) -> Result<Vec<crate::image_impl::Point2f>, EngineError> {
         use geo_clipper::Clipper;
         use geo_types::{Coord, LineString, Polygon};
@@ -730,6 +1449,64 @@ impl DBPostProcess {
         Ok(result)
     }
 
+    /// Same offset-expansion as `unclip_pure`, generalized to an
+    /// arbitrary-length polygon instead of a fixed 4-point box, for the
+    /// `"poly"` box-type path.
+    fn unclip_poly_pure(&self, pts: &[crate::image_impl::Point2f]) -> Result<Vec<crate::image_impl::Point2f>, EngineError> {
+        use geo_clipper::Clipper;
+        use geo_types::{Coord, LineString, Polygon};
+
+        let n = pts.len();
+        if n < 3 {
+            return Ok(pts.to_vec());
+        }
+
+        let mut area = 0.0f64;
+        let mut length = 0.0f64;
+        for i in 0..n {
+            let j = (i + 1) % n;
+            let dx = (pts[j].x - pts[i].x) as f64;
+            let dy = (pts[j].y - pts[i].y) as f64;
+            area += pts[i].x as f64 * pts[j].y as f64 - pts[j].x as f64 * pts[i].y as f64;
+            length += (dx * dx + dy * dy).sqrt();
+        }
+        area = area.abs() / 2.0;
+        if area <= 0.0 || length <= 0.0 {
+            return Ok(pts.to_vec());
+        }
+
+        let distance = area * self.unclip_ratio / length;
+
+        let coords: Vec<Coord<f64>> = pts
+            .iter()
+            .map(|p| Coord { x: p.x as f64, y: p.y as f64 })
+            .collect();
+
+        let mut ring = coords.clone();
+        ring.push(coords[0]);
+        let line_string = LineString(ring);
+        let poly = Polygon::new(line_string, vec![]);
+
+        let expanded = poly.offset(distance, geo_clipper::JoinType::Miter(2.0), geo_clipper::EndType::ClosedPolygon, 2.0);
+
+        let mut result = Vec::new();
+        if !expanded.0.is_empty() {
+            let first_poly = &expanded.0[0];
+            for coord in first_poly.exterior().0.iter() {
+                result.push(crate::image_impl::Point2f::new(coord.x as f32, coord.y as f32));
+            }
+            if let Some(last) = result.last() {
+                if let Some(first) = result.first() {
+                    if (last.x - first.x).abs() < 0.1 && (last.y - first.y).abs() < 0.1 {
+                        result.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     fn order_points_clockwise_pure(&self, pts: [crate::image_impl::Point2f; 4]) -> [crate::image_impl::Point2f; 4] {
         let mut pts_vec: Vec<crate::image_impl::Point2f> = pts.to_vec();
         pts_vec.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
@@ -744,21 +1521,153 @@ impl DBPostProcess {
             std::mem::swap(&mut tr, &mut br);
         }
 
-        [tl, tr, br, bl]
+        let ordered = [tl, tr, br, bl];
+        // The sort-and-swap above assumes a roughly axis-aligned box; for a
+        // box rotated close to 45 degrees it can pick an order that winds
+        // the wrong way. Verify with the signed shoelace area (positive for
+        // this tl/tr/br/bl convention) and re-wind around the same anchor
+        // point instead of trusting the sort unconditionally.
+        if signed_polygon_area(&ordered) < 0.0 {
+            [tl, bl, br, tr]
+        } else {
+            ordered
+        }
     }
 
+    /// Clip `points` to the image rectangle and re-fit the result to a
+    /// `Quad`. A box that exits the image on a diagonal edge is properly
+    /// cut at the boundary (Sutherland–Hodgman) rather than having each
+    /// corner clamped independently, which used to turn a valid rotated box
+    /// into a self-intersecting quadrilateral.
     fn clip_det_res_pure(
         &self,
-        mut points: [crate::image_impl::Point2f; 4],
+        points: [crate::image_impl::Point2f; 4],
         img_height: i32,
         img_width: i32,
     ) -> [crate::image_impl::Point2f; 4] {
-        for p in &mut points {
-            p.x = p.x.max(0.0).min((img_width - 1) as f32);
-            p.y = p.y.max(0.0).min((img_height - 1) as f32);
+        let clamp_each = |mut pts: [crate::image_impl::Point2f; 4]| {
+            for p in &mut pts {
+                p.x = p.x.max(0.0).min((img_width - 1) as f32);
+                p.y = p.y.max(0.0).min((img_height - 1) as f32);
+            }
+            pts
+        };
+
+        let clipped = clip_polygon_to_rect(&points, 0.0, 0.0, (img_width - 1) as f32, (img_height - 1) as f32);
+        if clipped.len() < 3 {
+            return clamp_each(points);
+        }
+
+        match mini_box_points_pure(&clipped) {
+            Ok(box_pts) => box_pts,
+            Err(_) => clamp_each(points),
+        }
+    }
+}
+
+/// Fit a `Quad` to an arbitrary-length point set via `min_area_rect`,
+/// ordered the same way as the rest of the pure-Rust pipeline
+/// (top-left, top-right, bottom-right, bottom-left).
+#[cfg(not(feature = "use-opencv"))]
+fn mini_box_points_pure(
+    pts: &[crate::image_impl::Point2f],
+) -> Result<[crate::image_impl::Point2f; 4], EngineError> {
+    use crate::image_impl::{box_points, min_area_rect};
+
+    let (center, size, angle) =
+        min_area_rect(pts).map_err(|e| EngineError::ImageError(e.to_string()))?;
+    let box_pts_raw = box_points(center, size, angle);
+
+    let mut sorted = box_pts_raw.to_vec();
+    sorted.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
+
+    let (mut tl, mut bl) = (sorted[0], sorted[1]);
+    if bl.y < tl.y {
+        std::mem::swap(&mut tl, &mut bl);
+    }
+    let (mut tr, mut br) = (sorted[2], sorted[3]);
+    if br.y < tr.y {
+        std::mem::swap(&mut tr, &mut br);
+    }
+
+    Ok([tl, tr, br, bl])
+}
+
+/// Signed shoelace area of an arbitrary-length polygon, with the last vertex
+/// paired back to the first. The sum is not negated, so in image coordinates
+/// (y growing downward) an on-screen clockwise order such as tl, tr, br, bl
+/// comes out positive: the opposite sign from `Contour::signed_area`.
+#[cfg(not(feature = "use-opencv"))]
+fn signed_polygon_area(points: &[crate::image_impl::Point2f]) -> f32 {
+    let n = points.len();
+    if n < 3 {
+        return 0.0;
+    }
+
+    let mut area = 0.0f32;
+    for i in 0..n {
+        let j = (i + 1) % n;
+        area += points[i].x * points[j].y - points[j].x * points[i].y;
+    }
+    area * 0.5
+}
+
+/// Sutherland–Hodgman clip of a (possibly non-convex) polygon against the
+/// axis-aligned rectangle `[min_x, max_x] x [min_y, max_y]`, clipping
+/// against each of the 4 boundary half-planes in turn.
+#[cfg(not(feature = "use-opencv"))]
+fn clip_polygon_to_rect(
+    points: &[crate::image_impl::Point2f],
+    min_x: f32,
+    min_y: f32,
+    max_x: f32,
+    max_y: f32,
+) -> Vec<crate::image_impl::Point2f> {
+    use crate::image_impl::Point2f;
+
+    fn clip_edge(
+        poly: &[Point2f],
+        inside: impl Fn(Point2f) -> bool,
+        intersect: impl Fn(Point2f, Point2f) -> Point2f,
+    ) -> Vec<Point2f> {
+        if poly.is_empty() {
+            return Vec::new();
         }
-        points
+        let n = poly.len();
+        let mut out = Vec::with_capacity(n + 1);
+        for i in 0..n {
+            let curr = poly[i];
+            let prev = poly[(i + n - 1) % n];
+            let curr_in = inside(curr);
+            let prev_in = inside(prev);
+            if curr_in {
+                if !prev_in {
+                    out.push(intersect(prev, curr));
+                }
+                out.push(curr);
+            } else if prev_in {
+                out.push(intersect(prev, curr));
+            }
+        }
+        out
+    }
+
+    fn lerp_x(p1: Point2f, p2: Point2f, x: f32) -> Point2f {
+        let t = if (p2.x - p1.x).abs() < 1e-9 { 0.0 } else { (x - p1.x) / (p2.x - p1.x) };
+        Point2f::new(x, p1.y + t * (p2.y - p1.y))
     }
+
+    fn lerp_y(p1: Point2f, p2: Point2f, y: f32) -> Point2f {
+        let t = if (p2.y - p1.y).abs() < 1e-9 { 0.0 } else { (y - p1.y) / (p2.y - p1.y) };
+        Point2f::new(p1.x + t * (p2.x - p1.x), y)
+    }
+
+    let mut poly = points.to_vec();
+    poly = clip_edge(&poly, |p| p.x >= min_x, |p1, p2| lerp_x(p1, p2, min_x));
+    poly = clip_edge(&poly, |p| p.x <= max_x, |p1, p2| lerp_x(p1, p2, max_x));
+    poly = clip_edge(&poly, |p| p.y >= min_y, |p1, p2| lerp_y(p1, p2, min_y));
+    poly = clip_edge(&poly, |p| p.y <= max_y, |p1, p2| lerp_y(p1, p2, max_y));
+    poly
 }
 
 // Helper functions for pure Rust implementation
@@ -787,44 +1696,235 @@ fn dilate_2x2(img: &image::GrayImage) -> image::GrayImage {
     result
 }
 
+/// Even-odd-rule x-intersections of the horizontal line `y` with each edge
+/// of `polygon`, unsorted. Scanning a row this way once and filling the
+/// spans between consecutive intersections is equivalent to testing every
+/// pixel in the row with a point-in-polygon check, but touches each edge
+/// once per row instead of once per pixel.
 #[cfg(not(feature = "use-opencv"))]
-fn point_in_polygon(x: f32, y: f32, polygon: &[crate::image_impl::Point2f; 4]) -> bool {
-    let mut inside = false;
-    let mut j = polygon.len() - 1;
+fn scanline_intersections(y: f32, polygon: &[crate::image_impl::Point2f]) -> Vec<f32> {
+    let n = polygon.len();
+    let mut xs = Vec::new();
+    let mut j = n - 1;
 
-    for i in 0..polygon.len() {
-        let xi = polygon[i].x;
+    for i in 0..n {
         let yi = polygon[i].y;
-        let xj = polygon[j].x;
         let yj = polygon[j].y;
+        if (yi > y) != (yj > y) {
+            let xi = polygon[i].x;
+            let xj = polygon[j].x;
+            xs.push(xi + (y - yi) * (xj - xi) / (yj - yi));
+        }
+        j = i;
+    }
+
+    xs
+}
 
-        let intersect = ((yi > y) != (yj > y)) && (x < (xj - xi) * (y - yi) / (yj - yi) + xi);
-        if intersect {
-            inside = !inside;
+/// Signed distance from `(x, y)` to the boundary of `polygon`, matching
+/// OpenCV's `pointPolygonTest(..., measureDist=true)`: positive when the
+/// point is inside, negative when outside, zero on an edge. The magnitude is
+/// the minimum distance to any edge segment (projecting onto each segment
+/// and clamping to its endpoints); the sign comes from the same even-odd
+/// ray-casting rule used by `scanline_intersections` to decide inside/outside.
+/// Used by the box-accumulation loop above to drop a candidate box whose
+/// corners all fall inside one already accepted, a more robust duplicate
+/// check than a coarse IoU/area threshold.
+#[cfg(not(feature = "use-opencv"))]
+fn point_polygon_distance(x: f32, y: f32, polygon: &[crate::image_impl::Point2f]) -> f32 {
+    let n = polygon.len();
+    if n == 0 {
+        return f32::NEG_INFINITY;
+    }
+
+    let mut min_dist = f32::MAX;
+    let mut inside = false;
+    let mut j = n - 1;
+
+    for i in 0..n {
+        let pi = polygon[i];
+        let pj = polygon[j];
+
+        let dx = pj.x - pi.x;
+        let dy = pj.y - pi.y;
+        let len_sq = dx * dx + dy * dy;
+        let t = if len_sq > 1e-12 {
+            (((x - pi.x) * dx + (y - pi.y) * dy) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let proj_x = pi.x + t * dx;
+        let proj_y = pi.y + t * dy;
+        let seg_dist = ((x - proj_x).powi(2) + (y - proj_y).powi(2)).sqrt();
+        min_dist = min_dist.min(seg_dist);
+
+        if (pi.y > y) != (pj.y > y) {
+            let x_at_y = pi.x + (y - pi.y) * dx / dy;
+            if x < x_at_y {
+                inside = !inside;
+            }
         }
+
         j = i;
     }
 
-    inside
+    if inside {
+        min_dist
+    } else {
+        -min_dist
+    }
 }
 
 #[cfg(not(feature = "use-opencv"))]
 fn calculate_contour_area(contour: &crate::contours::Contour) -> f32 {
-    if contour.points.len() < 3 {
+    contour.signed_area().abs()
+}
+
+/// Shoelace area of a closed polygon given as `Point2f`s, used to rank
+/// `marching_squares_supersampled`'s sub-pixel polylines the same way
+/// `calculate_contour_area` ranks `find_contours`'s integer ones.
+#[cfg(not(feature = "use-opencv"))]
+fn polygon_area_f32(points: &[crate::image_impl::Point2f]) -> f32 {
+    let n = points.len();
+    if n < 3 {
         return 0.0;
     }
-    
-    // Use shoelace formula to calculate polygon area
+
     let mut area = 0.0f32;
+    for i in 0..n {
+        let j = (i + 1) % n;
+        area += points[i].x * points[j].y - points[j].x * points[i].y;
+    }
+    (area * 0.5).abs()
+}
+
+#[cfg(not(feature = "use-opencv"))]
+fn contour_perimeter(contour: &crate::contours::Contour) -> f32 {
+    if contour.points.len() < 2 {
+        return 0.0;
+    }
+
     let n = contour.points.len();
-    
+    let mut perimeter = 0.0f32;
     for i in 0..n {
         let j = (i + 1) % n;
         let (x1, y1) = contour.points[i];
         let (x2, y2) = contour.points[j];
-        area += (x1 as f32 * y2 as f32) - (x2 as f32 * y1 as f32);
+        perimeter += (((x2 - x1) as f32).powi(2) + ((y2 - y1) as f32).powi(2)).sqrt();
+    }
+    perimeter
+}
+
+/// Ramer–Douglas–Peucker simplification of `contour`, matching OpenCV's
+/// `approxPolyDP`: find the vertex farthest (perpendicular distance) from
+/// the line through the segment's two endpoints; if that distance exceeds
+/// `epsilon`, keep the vertex and recurse on both halves, otherwise collapse
+/// the whole segment to just its endpoints. `closed` treats the contour as a
+/// loop (splitting it into two open arcs at its farthest-apart point pair
+/// before recursing) rather than a polyline with fixed first/last points.
+/// The recursion itself lives in `crate::rdp`, shared with
+/// `contours::approx_poly_dp` and `image_impl`'s own `approx_poly_dp`.
+///
+/// Unlike `clip_det_res_pure`/`order_points_clockwise_pure`, this makes no
+/// assumption about the output length, so it's suitable for curved or
+/// many-sided text regions that a min-area-rect quad would flatten.
+#[cfg(not(feature = "use-opencv"))]
+pub fn approx_poly_dp(
+    contour: &crate::contours::Contour,
+    epsilon: f32,
+    closed: bool,
+) -> Vec<crate::image_impl::Point2f> {
+    let pts: Vec<(f64, f64)> = contour
+        .points
+        .iter()
+        .map(|&(x, y)| (x as f64, y as f64))
+        .collect();
+
+    if pts.len() < 3 {
+        return pts
+            .into_iter()
+            .map(|(x, y)| crate::image_impl::Point2f::new(x as f32, y as f32))
+            .collect();
+    }
+
+    let simplified = if closed {
+        crate::rdp::simplify_closed(&pts, epsilon as f64)
+    } else {
+        crate::rdp::simplify_open(&pts, epsilon as f64)
+    };
+    simplified
+        .into_iter()
+        .map(|(x, y)| crate::image_impl::Point2f::new(x as f32, y as f32))
+        .collect()
+}
+
+#[cfg(all(test, not(feature = "use-opencv")))]
+mod tests {
+    use super::*;
+    use crate::image_impl::Point2f;
+
+    fn post() -> DBPostProcess {
+        DBPostProcess::new(0.3, 0.6, 1000, 1.5, false)
+    }
+
+    #[test]
+    fn test_clip_det_res_pure_box_fully_inside_is_unchanged() {
+        let pts = [
+            Point2f::new(10.0, 10.0),
+            Point2f::new(20.0, 10.0),
+            Point2f::new(20.0, 20.0),
+            Point2f::new(10.0, 20.0),
+        ];
+        let clipped = post().clip_det_res_pure(pts, 100, 100);
+        for (a, b) in pts.iter().zip(clipped.iter()) {
+            assert!((a.x - b.x).abs() < 1e-3);
+            assert!((a.y - b.y).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_point_polygon_distance_sign_and_magnitude() {
+        // A 10x10 square with corners at (0,0)-(10,10).
+        let square = [
+            Point2f::new(0.0, 0.0),
+            Point2f::new(10.0, 0.0),
+            Point2f::new(10.0, 10.0),
+            Point2f::new(0.0, 10.0),
+        ];
+
+        // Center is inside, 5 units from every edge.
+        let center = point_polygon_distance(5.0, 5.0, &square);
+        assert!(center > 0.0);
+        assert!((center - 5.0).abs() < 1e-3);
+
+        // A point 3 units outside the left edge.
+        let outside = point_polygon_distance(-3.0, 5.0, &square);
+        assert!(outside < 0.0);
+        assert!((outside + 3.0).abs() < 1e-3);
+
+        // A point exactly on an edge is distance zero.
+        let on_edge = point_polygon_distance(0.0, 5.0, &square);
+        assert!(on_edge.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_clip_det_res_pure_corner_outside_is_cut_not_sheared() {
+        // A box whose top-right corner sits well outside a 20x20 image. True
+        // Sutherland-Hodgman clipping cuts along the boundary, so every
+        // clipped vertex must stay within the image rectangle -- per-point
+        // clamping would instead drag that corner to (19, 0), shearing the
+        // box into a shape that no longer traces the original edges.
+        let pts = [
+            Point2f::new(5.0, 5.0),
+            Point2f::new(30.0, -10.0),
+            Point2f::new(30.0, 15.0),
+            Point2f::new(5.0, 15.0),
+        ];
+        let clipped = post().clip_det_res_pure(pts, 20, 20);
+        for p in &clipped {
+            assert!(p.x >= -1e-3 && p.x <= 19.0 + 1e-3);
+            assert!(p.y >= -1e-3 && p.y <= 19.0 + 1e-3);
+        }
     }
-    
-    (area * 0.5).abs()
 }
 
@@ -10,21 +10,70 @@ use opencv::core::Mat;
 use crate::image_impl::Mat;
 
 use crate::engine::EngineError;
+use crate::types::InputColor;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 pub struct DetPreProcess {
     pub limit_side_len: i32,
     pub limit_type: String,
     pub mean: [f32; 3],
     pub std: [f32; 3],
+    pub input_color: InputColor,
+    pub background: [u8; 3],
+    pub preprocess_threads: usize,
 }
 
 impl DetPreProcess {
-    pub fn new(limit_side_len: i32, limit_type: String, mean: [f32; 3], std: [f32; 3]) -> Self {
+    pub fn new(
+        limit_side_len: i32,
+        limit_type: String,
+        mean: [f32; 3],
+        std: [f32; 3],
+        input_color: InputColor,
+        background: [u8; 3],
+        preprocess_threads: usize,
+    ) -> Self {
         Self {
             limit_side_len,
             limit_type,
             mean,
             std,
+            input_color,
+            background,
+            preprocess_threads,
+        }
+    }
+
+    /// Fetch every row of `img` as `[r, g, b]` pixels, in parallel across
+    /// rows when built with the `parallel` feature (capped at
+    /// `preprocess_threads` workers, or rayon's global pool when `0`).
+    /// Hoisting the row fetch out of `normalize_and_permute`'s write loop
+    /// means color-mode detection and the `Mat` accessor call happen once
+    /// per row instead of once per pixel.
+    fn fetch_rows(&self, img: &Mat, h: usize, w: usize) -> Result<Vec<Vec<[u8; 3]>>, EngineError> {
+        let fetch = |y: i32| {
+            crate::image_impl::sample_rgb_row(img, y, w as i32, self.input_color, self.background)
+                .map_err(|e| EngineError::Preprocess(e.to_string()))
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            if self.preprocess_threads > 0 {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(self.preprocess_threads)
+                    .build()
+                    .map_err(|e| EngineError::Preprocess(e.to_string()))?;
+                pool.install(|| (0..h as i32).into_par_iter().map(fetch).collect())
+            } else {
+                (0..h as i32).into_par_iter().map(fetch).collect()
+            }
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            (0..h as i32).map(fetch).collect()
         }
     }
 
@@ -92,21 +141,23 @@ impl DetPreProcess {
         Ok(dst)
     }
 
-    #[cfg(feature = "use-opencv")]
+    // CRITICAL: the model was trained on BGR pixel order, so channel 0 of
+    // `out` always holds blue, channel 1 green, channel 2 red, regardless of
+    // which backend's `sample_rgb_row` supplied the `[r, g, b]` pixels below.
     fn normalize_and_permute(&self, img: &Mat) -> Result<Array4<f32>, EngineError> {
         let size = img.size()?;
         let h = size.height as usize;
         let w = size.width as usize;
 
+        let rows = self.fetch_rows(img, h, w)?;
         let mut out = Array4::<f32>::zeros((1, 3, h, w));
         let scale = 1.0 / 255.0;
 
-        for y in 0..h {
-            for x in 0..w {
-                let pix = img.at_2d::<core::Vec3b>(y as i32, x as i32)?;
-                let b = pix[0] as f32 * scale;
-                let g = pix[1] as f32 * scale;
-                let r = pix[2] as f32 * scale;
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, [r, g, b]) in row.into_iter().enumerate() {
+                let r = r as f32 * scale;
+                let g = g as f32 * scale;
+                let b = b as f32 * scale;
 
                 out[[0, 0, y, x]] = (b - self.mean[0]) / self.std[0];
                 out[[0, 1, y, x]] = (g - self.mean[1]) / self.std[1];
@@ -116,41 +167,5 @@ impl DetPreProcess {
 
         Ok(out)
     }
-    
-    #[cfg(not(feature = "use-opencv"))]
-    fn normalize_and_permute(&self, img: &Mat) -> Result<Array4<f32>, EngineError> {
-        let size = img.size()?;
-        let h = size.height as usize;
-        let w = size.width as usize;
-
-        let mut out = ndarray::Array4::<f32>::zeros((1, 3, h, w));
-        let scale = 1.0 / 255.0;
-        
-        // Cache normalization parameters
-        let mean_b = self.mean[0];
-        let mean_g = self.mean[1];
-        let mean_r = self.mean[2];
-        let std_b = self.std[0];
-        let std_g = self.std[1];
-        let std_r = self.std[2];
-
-        for y in 0..h {
-            for x in 0..w {
-                let pix = img.get_pixel(x as u32, y as u32);
-                // CRITICAL: image crate loads as RGB, but OpenCV uses BGR
-                // Model was trained on BGR, so we must convert RGB -> BGR
-                let r = pix[0] as f32 * scale;  // Red channel
-                let g = pix[1] as f32 * scale;  // Green channel
-                let b = pix[2] as f32 * scale;  // Blue channel
-
-                // Store in BGR order to match OpenCV
-                out[[0, 0, y, x]] = (b - mean_b) / std_b;  // Blue
-                out[[0, 1, y, x]] = (g - mean_g) / std_g;  // Green  
-                out[[0, 2, y, x]] = (r - mean_r) / std_r;  // Red
-            }
-        }
-
-        Ok(out)
-    }
 }
 
@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 #[cfg(feature = "use-opencv")]
 use opencv::{core::{Mat, Point2f}, prelude::MatTraitConst};
@@ -7,24 +8,51 @@ use opencv::{core::{Mat, Point2f}, prelude::MatTraitConst};
 use crate::image_impl::{Mat, Point2f};
 
 use crate::cal_rec_boxes::CalRecBoxes;
+use crate::cls::TextClassifier;
 use crate::det::TextDetector;
 use crate::engine::EngineError;
-use crate::geometry::{apply_vertical_padding, map_boxes_to_original, resize_image_within_bounds, get_rotate_crop_image, OpRecord};
+use crate::geometry::{apply_vertical_padding, map_boxes_to_original, resize_image_within_bounds, crop_quad, OpRecord, Quad};
 use crate::rec::{TextRecOutput, TextRecognizer};
-use crate::types::{DetConfig, GlobalConfig, RecConfig};
+use crate::types::{ClsConfig, DetConfig, GlobalConfig, RecConfig};
 
 pub struct RapidOcrOutput {
-    pub boxes: Vec<[Point2f; 4]>,
+    pub boxes: Vec<Quad>,
     pub txts: Vec<String>,
     pub scores: Vec<f32>,
     pub word_results: Vec<Vec<(String, f32, [Point2f; 4])>>,
+    /// Per-box direction-classifier label ("0"/"180") and confidence, in the
+    /// same order as `boxes`/`txts`. Empty when `global.use_cls` is off, so
+    /// callers who don't use the classifier don't have to account for it.
+    pub cls_angles: Vec<String>,
+    pub cls_scores: Vec<f32>,
     pub elapse_det: f64,
     pub elapse_rec: f64,
 }
 
+/// A detection-stage box: the quad and its detection confidence, with no
+/// recognition performed yet. Returned by `RapidOcr::detect`/`detect_on_mat`
+/// for callers who only need text-region layout, and accepted back by
+/// `RapidOcr::recognize` so detection doesn't need to be re-run.
+pub struct DetectedBox {
+    pub quad: Quad,
+    pub score: f32,
+}
+
+/// Per-stage wall-clock breakdown for `RapidOcr::run_timed`/`run_on_mat_timed`,
+/// mirroring the stages `run_on_mat` performs in order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OcrTimings {
+    pub preprocess: Duration,
+    pub detection: Duration,
+    pub crop: Duration,
+    pub recognition: Duration,
+    pub postprocess: Duration,
+}
+
 pub struct RapidOcr {
     pub det: TextDetector,
     pub rec: TextRecognizer,
+    pub cls: Option<TextClassifier>,
     pub global: GlobalConfig,
     pub cal_rec_boxes: CalRecBoxes,
 }
@@ -44,17 +72,31 @@ impl RapidOcr {
         let rec = TextRecognizer::new(rec_cfg.clone())?;
         let cal_rec_boxes = CalRecBoxes::new();
 
-        Ok(Self { det, rec, global, cal_rec_boxes })
+        Ok(Self { det, rec, cls: None, global, cal_rec_boxes })
+    }
+
+    /// Construct a PP-OCRv5 pipeline with the text-direction classifier enabled.
+    pub fn new_ppv5_with_cls<P: AsRef<Path>>(
+        det_model: P,
+        rec_model: P,
+        dict_path: P,
+        cls_model: P,
+    ) -> Result<Self, EngineError> {
+        let mut ocr = Self::new_ppv5(det_model, rec_model, dict_path)?;
+        let cls_cfg = ClsConfig::ppv5(cls_model.as_ref().to_path_buf());
+        ocr.cls = Some(TextClassifier::new(cls_cfg)?);
+        ocr.global.use_cls = true;
+        Ok(ocr)
     }
 
     /// Run OCR on an image file (convenience wrapper for run_on_mat)
-    pub fn run<P: AsRef<Path>>(&self, image_path: P) -> Result<RapidOcrOutput, EngineError> {
+    pub fn run<P: AsRef<Path>>(&mut self, image_path: P) -> Result<RapidOcrOutput, EngineError> {
         use crate::image_impl::imread;
         let img = imread(image_path)?;
         self.run_on_mat(&img)
     }
 
-    pub fn run_on_mat(&self, img: &Mat) -> Result<RapidOcrOutput, EngineError> {
+    pub fn run_on_mat(&mut self, img: &Mat) -> Result<RapidOcrOutput, EngineError> {
         let size = img.size()?;
         let ori_h = size.height;
         let ori_w = size.width;
@@ -91,16 +133,23 @@ impl RapidOcr {
                     txts: Vec::new(),
                     scores: Vec::new(),
                     word_results: Vec::new(),
+                    cls_angles: Vec::new(),
+                    cls_scores: Vec::new(),
                     elapse_det: det_res.elapse,
                     elapse_rec: 0.0,
                 })
             }
         };
 
+        let mut padded_boxes = padded_boxes;
+        if self.global.sort_boxes {
+            sort_boxes_reading_order(&mut padded_boxes);
+        }
+
         // Crop text regions from padded image using padded-space boxes
         let mut crop_imgs: Vec<Mat> = Vec::with_capacity(padded_boxes.len());
         for b in &padded_boxes {
-            let crop = get_rotate_crop_image(&padded, b)?;
+            let crop = crop_quad(&padded, b, self.global.rectify_quads)?;
             crop_imgs.push(crop);
         }
 
@@ -108,8 +157,19 @@ impl RapidOcr {
         let mut boxes = padded_boxes.clone();
         map_boxes_to_original(&mut boxes, &op_record, ori_h, ori_w);
 
+        // Text-direction classification: correct upside-down crops before recognition
+        let (mut cls_angles, mut cls_scores) = (vec![String::new(); boxes.len()], vec![0.0; boxes.len()]);
+        if self.global.use_cls {
+            if let Some(cls) = self.cls.as_mut() {
+                let cls_res = cls.run(&crop_imgs)?;
+                crop_imgs = cls_res.imgs;
+                cls_angles = cls_res.angles;
+                cls_scores = cls_res.scores;
+            }
+        }
+
         // Recognition
-        let rec_res: TextRecOutput = self.rec.run(&crop_imgs, self.global.return_word_box)?;
+        let rec_res: TextRecOutput = self.rec.run(&crop_imgs, self.global.return_word_box, false)?;
 
         // Optional word boxes (computed before we move fields out of rec_res)
         let word_results_all: Vec<Vec<(String, f32, [Point2f; 4])>> = if self.global.return_word_box {
@@ -128,18 +188,27 @@ impl RapidOcr {
         let mut f_txts = Vec::new();
         let mut f_scores = Vec::new();
         let mut f_word_results: Vec<Vec<(String, f32, [Point2f; 4])>> = Vec::new();
+        let mut f_cls_angles = Vec::new();
+        let mut f_cls_scores = Vec::new();
 
-        eprintln!("[RapidOCR] Filtering {} boxes by text_score threshold {}", boxes.len(), self.global.text_score);
+        log::debug!(
+            "filtering {} boxes by text_score threshold {}",
+            boxes.len(),
+            self.global.text_score
+        );
         for (idx, (b, (t, s))) in boxes
             .into_iter()
             .zip(txts.drain(..).zip(scores.drain(..)))
             .enumerate()
         {
             if s < self.global.text_score {
-                eprintln!("[RapidOCR] Box {} rejected: rec_score={:.3} < {}, text=\"{}\"", idx, s, self.global.text_score, t);
+                log::trace!(
+                    "box {} rejected: rec_score={:.3} < {}, text=\"{}\"",
+                    idx, s, self.global.text_score, t
+                );
                 continue;
             }
-            eprintln!("[RapidOCR] Box {} ACCEPTED: rec_score={:.3}, text=\"{}\"", idx, s, t);
+            log::trace!("box {} accepted: rec_score={:.3}, text=\"{}\"", idx, s, t);
             f_boxes.push(b);
             f_txts.push(t);
             f_scores.push(s);
@@ -149,6 +218,9 @@ impl RapidOcr {
             } else {
                 f_word_results.push(Vec::new());
             }
+
+            f_cls_angles.push(std::mem::take(&mut cls_angles[idx]));
+            f_cls_scores.push(cls_scores[idx]);
         }
 
         Ok(RapidOcrOutput {
@@ -156,9 +228,567 @@ impl RapidOcr {
             txts: f_txts,
             scores: f_scores,
             word_results: f_word_results,
+            cls_angles: f_cls_angles,
+            cls_scores: f_cls_scores,
             elapse_det: det_res.elapse,
             elapse_rec: rec_res.elapse,
         })
     }
+
+    /// Run only the detection stage: resize/pad, detect, and map the
+    /// resulting boxes back to `image_path`'s original coordinates. Skips
+    /// cropping, classification and recognition entirely, for callers who
+    /// only need text-region layout.
+    pub fn detect<P: AsRef<Path>>(&mut self, image_path: P) -> Result<Vec<DetectedBox>, EngineError> {
+        use crate::image_impl::imread;
+        let img = imread(image_path)?;
+        self.detect_on_mat(&img)
+    }
+
+    pub fn detect_on_mat(&mut self, img: &Mat) -> Result<Vec<DetectedBox>, EngineError> {
+        let size = img.size()?;
+        let ori_h = size.height;
+        let ori_w = size.width;
+
+        let mut op_record: OpRecord = OpRecord::new();
+        let (resized, ratio_h, ratio_w) = resize_image_within_bounds(
+            img,
+            self.global.min_side_len,
+            self.global.max_side_len,
+        )?;
+        let mut m = std::collections::BTreeMap::new();
+        m.insert("ratio_h".to_string(), ratio_h);
+        m.insert("ratio_w".to_string(), ratio_w);
+        op_record.insert("preprocess".to_string(), m);
+
+        let (padded, op_record) = apply_vertical_padding(
+            &resized,
+            op_record,
+            self.global.width_height_ratio,
+            self.global.min_height,
+        )?;
+
+        let det_res = self.det.run(&padded)?;
+        let mut boxes = match det_res.boxes {
+            Some(b) if !b.is_empty() => b,
+            _ => return Ok(Vec::new()),
+        };
+        let scores = det_res.scores.unwrap_or_default();
+        map_boxes_to_original(&mut boxes, &op_record, ori_h, ori_w);
+
+        Ok(boxes
+            .into_iter()
+            .zip(scores)
+            .map(|(quad, score)| DetectedBox { quad, score })
+            .collect())
+    }
+
+    /// Run recognition (and direction classification, if enabled) over boxes
+    /// already in `img`'s coordinate space, such as those returned by
+    /// `detect`/`detect_on_mat`. Pairs with `detect` so callers can reuse
+    /// detection output instead of re-running it.
+    pub fn recognize(
+        &mut self,
+        img: &Mat,
+        boxes: &[DetectedBox],
+    ) -> Result<(Vec<String>, Vec<f32>), EngineError> {
+        let mut crop_imgs: Vec<Mat> = Vec::with_capacity(boxes.len());
+        for b in boxes {
+            crop_imgs.push(crop_quad(img, &b.quad, self.global.rectify_quads)?);
+        }
+
+        if self.global.use_cls {
+            if let Some(cls) = self.cls.as_mut() {
+                let cls_res = cls.run(&crop_imgs)?;
+                crop_imgs = cls_res.imgs;
+            }
+        }
+
+        let rec_res = self.rec.run(&crop_imgs, false, false)?;
+        Ok((rec_res.txts, rec_res.scores))
+    }
+
+    /// Run OCR on an image file like `run`, but also return a per-stage
+    /// timing breakdown (preprocess, detection, crop/rectify, recognition,
+    /// postprocess), so callers and the benchmark harness can see where time
+    /// actually goes instead of only one end-to-end duration.
+    pub fn run_timed<P: AsRef<Path>>(
+        &mut self,
+        image_path: P,
+    ) -> Result<(RapidOcrOutput, OcrTimings), EngineError> {
+        use crate::image_impl::imread;
+        let img = imread(image_path)?;
+        self.run_on_mat_timed(&img)
+    }
+
+    pub fn run_on_mat_timed(&mut self, img: &Mat) -> Result<(RapidOcrOutput, OcrTimings), EngineError> {
+        let mut timings = OcrTimings::default();
+        let size = img.size()?;
+        let ori_h = size.height;
+        let ori_w = size.width;
+
+        let t_preprocess = Instant::now();
+        let mut op_record: OpRecord = OpRecord::new();
+        let (resized, ratio_h, ratio_w) = resize_image_within_bounds(
+            img,
+            self.global.min_side_len,
+            self.global.max_side_len,
+        )?;
+        let mut m = std::collections::BTreeMap::new();
+        m.insert("ratio_h".to_string(), ratio_h);
+        m.insert("ratio_w".to_string(), ratio_w);
+        op_record.insert("preprocess".to_string(), m);
+        let (padded, op_record) = apply_vertical_padding(
+            &resized,
+            op_record,
+            self.global.width_height_ratio,
+            self.global.min_height,
+        )?;
+        timings.preprocess = t_preprocess.elapsed();
+
+        let t_detection = Instant::now();
+        let det_res = self.det.run(&padded)?;
+        let padded_boxes = match det_res.boxes {
+            Some(b) if !b.is_empty() => b,
+            _ => {
+                timings.detection = t_detection.elapsed();
+                return Ok((
+                    RapidOcrOutput {
+                        boxes: Vec::new(),
+                        txts: Vec::new(),
+                        scores: Vec::new(),
+                        word_results: Vec::new(),
+                        cls_angles: Vec::new(),
+                        cls_scores: Vec::new(),
+                        elapse_det: det_res.elapse,
+                        elapse_rec: 0.0,
+                    },
+                    timings,
+                ));
+            }
+        };
+        timings.detection = t_detection.elapsed();
+
+        let t_crop = Instant::now();
+        let mut padded_boxes = padded_boxes;
+        if self.global.sort_boxes {
+            sort_boxes_reading_order(&mut padded_boxes);
+        }
+        let mut crop_imgs: Vec<Mat> = Vec::with_capacity(padded_boxes.len());
+        for b in &padded_boxes {
+            let crop = crop_quad(&padded, b, self.global.rectify_quads)?;
+            crop_imgs.push(crop);
+        }
+        let mut boxes = padded_boxes.clone();
+        map_boxes_to_original(&mut boxes, &op_record, ori_h, ori_w);
+        timings.crop = t_crop.elapsed();
+
+        let t_recognition = Instant::now();
+        let (mut cls_angles, mut cls_scores) = (vec![String::new(); boxes.len()], vec![0.0; boxes.len()]);
+        if self.global.use_cls {
+            if let Some(cls) = self.cls.as_mut() {
+                let cls_res = cls.run(&crop_imgs)?;
+                crop_imgs = cls_res.imgs;
+                cls_angles = cls_res.angles;
+                cls_scores = cls_res.scores;
+            }
+        }
+        let rec_res: TextRecOutput = self.rec.run(&crop_imgs, self.global.return_word_box, false)?;
+        timings.recognition = t_recognition.elapsed();
+
+        let t_postprocess = Instant::now();
+        let word_results_all: Vec<Vec<(String, f32, [Point2f; 4])>> = if self.global.return_word_box {
+            self
+                .cal_rec_boxes
+                .calc_word_boxes(&boxes, &rec_res, self.global.return_single_char_box)
+        } else {
+            vec![Vec::new(); boxes.len()]
+        };
+
+        let mut txts = rec_res.txts;
+        let mut scores = rec_res.scores;
+
+        let mut f_boxes = Vec::new();
+        let mut f_txts = Vec::new();
+        let mut f_scores = Vec::new();
+        let mut f_word_results: Vec<Vec<(String, f32, [Point2f; 4])>> = Vec::new();
+        let mut f_cls_angles = Vec::new();
+        let mut f_cls_scores = Vec::new();
+
+        for (idx, (b, (t, s))) in boxes
+            .into_iter()
+            .zip(txts.drain(..).zip(scores.drain(..)))
+            .enumerate()
+        {
+            if s < self.global.text_score {
+                continue;
+            }
+            f_boxes.push(b);
+            f_txts.push(t);
+            f_scores.push(s);
+
+            if idx < word_results_all.len() {
+                f_word_results.push(word_results_all[idx].clone());
+            } else {
+                f_word_results.push(Vec::new());
+            }
+
+            f_cls_angles.push(std::mem::take(&mut cls_angles[idx]));
+            f_cls_scores.push(cls_scores[idx]);
+        }
+        timings.postprocess = t_postprocess.elapsed();
+
+        Ok((
+            RapidOcrOutput {
+                boxes: f_boxes,
+                txts: f_txts,
+                scores: f_scores,
+                word_results: f_word_results,
+                cls_angles: f_cls_angles,
+                cls_scores: f_cls_scores,
+                elapse_det: det_res.elapse,
+                elapse_rec: rec_res.elapse,
+            },
+            timings,
+        ))
+    }
+
+    /// Run OCR on many already-decoded images, sharing `self`'s det/rec/cls
+    /// sessions across all of them instead of spinning up one pipeline per
+    /// image (contrast `run_batch`, which clones a pipeline per worker
+    /// thread). Detection and cropping still run one image at a time, but
+    /// every image's crops are concatenated before one `cls.run` call and one
+    /// `self.rec.run` call, so the recognizer batches across image
+    /// boundaries (in `RecConfig::rec_batch_num`-sized groups) instead of
+    /// paying a separate small batch — and a separate tensor resize in
+    /// `MnnSession::run` — per image.
+    pub fn run_batch_mats(&mut self, imgs: &[Mat]) -> Result<Vec<RapidOcrOutput>, EngineError> {
+        if imgs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        struct PerImage {
+            boxes: Vec<Quad>,
+            crop_range: std::ops::Range<usize>,
+            elapse_det: f64,
+        }
+
+        let mut per_image = Vec::with_capacity(imgs.len());
+        let mut all_crops: Vec<Mat> = Vec::new();
+
+        for img in imgs {
+            let size = img.size()?;
+            let ori_h = size.height;
+            let ori_w = size.width;
+
+            let mut op_record: OpRecord = OpRecord::new();
+            let (resized, ratio_h, ratio_w) = resize_image_within_bounds(
+                img,
+                self.global.min_side_len,
+                self.global.max_side_len,
+            )?;
+            let mut m = std::collections::BTreeMap::new();
+            m.insert("ratio_h".to_string(), ratio_h);
+            m.insert("ratio_w".to_string(), ratio_w);
+            op_record.insert("preprocess".to_string(), m);
+
+            let (padded, op_record) = apply_vertical_padding(
+                &resized,
+                op_record,
+                self.global.width_height_ratio,
+                self.global.min_height,
+            )?;
+
+            let det_res = self.det.run(&padded)?;
+            let padded_boxes = match det_res.boxes {
+                Some(b) if !b.is_empty() => b,
+                _ => {
+                    let start = all_crops.len();
+                    per_image.push(PerImage {
+                        boxes: Vec::new(),
+                        crop_range: start..start,
+                        elapse_det: det_res.elapse,
+                    });
+                    continue;
+                }
+            };
+
+            let mut padded_boxes = padded_boxes;
+            if self.global.sort_boxes {
+                sort_boxes_reading_order(&mut padded_boxes);
+            }
+
+            let start = all_crops.len();
+            for b in &padded_boxes {
+                all_crops.push(crop_quad(&padded, b, self.global.rectify_quads)?);
+            }
+            let crop_range = start..all_crops.len();
+
+            let mut boxes = padded_boxes;
+            map_boxes_to_original(&mut boxes, &op_record, ori_h, ori_w);
+
+            per_image.push(PerImage {
+                boxes,
+                crop_range,
+                elapse_det: det_res.elapse,
+            });
+        }
+
+        // Text-direction classification over every crop at once, same
+        // rationale as batching recognition below.
+        let (mut cls_angles, mut cls_scores) = (
+            vec![String::new(); all_crops.len()],
+            vec![0.0; all_crops.len()],
+        );
+        if self.global.use_cls && !all_crops.is_empty() {
+            if let Some(cls) = self.cls.as_mut() {
+                let cls_res = cls.run(&all_crops)?;
+                all_crops = cls_res.imgs;
+                cls_angles = cls_res.angles;
+                cls_scores = cls_res.scores;
+            }
+        }
+
+        // One batched recognition call across every image's crops, instead
+        // of one call per image.
+        let rec_res: TextRecOutput = self.rec.run(&all_crops, self.global.return_word_box, false)?;
+
+        let mut outputs = Vec::with_capacity(per_image.len());
+        for info in per_image {
+            let PerImage { boxes, crop_range, elapse_det } = info;
+
+            if boxes.is_empty() {
+                outputs.push(RapidOcrOutput {
+                    boxes: Vec::new(),
+                    txts: Vec::new(),
+                    scores: Vec::new(),
+                    word_results: Vec::new(),
+                    cls_angles: Vec::new(),
+                    cls_scores: Vec::new(),
+                    elapse_det,
+                    elapse_rec: 0.0,
+                });
+                continue;
+            }
+
+            let sub_rec = TextRecOutput {
+                imgs: Vec::new(),
+                txts: rec_res.txts[crop_range.clone()].to_vec(),
+                scores: rec_res.scores[crop_range.clone()].to_vec(),
+                word_infos: rec_res.word_infos[crop_range.clone()].to_vec(),
+                raw_logits: Vec::new(),
+                elapse: rec_res.elapse,
+            };
+
+            let word_results_all: Vec<Vec<(String, f32, [Point2f; 4])>> = if self.global.return_word_box {
+                self.cal_rec_boxes
+                    .calc_word_boxes(&boxes, &sub_rec, self.global.return_single_char_box)
+            } else {
+                vec![Vec::new(); boxes.len()]
+            };
+
+            let mut txts = sub_rec.txts;
+            let mut scores = sub_rec.scores;
+            let mut angles: Vec<String> = cls_angles[crop_range.clone()].to_vec();
+            let image_cls_scores = &cls_scores[crop_range.clone()];
+
+            let mut f_boxes = Vec::new();
+            let mut f_txts = Vec::new();
+            let mut f_scores = Vec::new();
+            let mut f_word_results: Vec<Vec<(String, f32, [Point2f; 4])>> = Vec::new();
+            let mut f_cls_angles = Vec::new();
+            let mut f_cls_scores = Vec::new();
+
+            for (idx, (b, (t, s))) in boxes
+                .into_iter()
+                .zip(txts.drain(..).zip(scores.drain(..)))
+                .enumerate()
+            {
+                if s < self.global.text_score {
+                    continue;
+                }
+                f_boxes.push(b);
+                f_txts.push(t);
+                f_scores.push(s);
+
+                if idx < word_results_all.len() {
+                    f_word_results.push(word_results_all[idx].clone());
+                } else {
+                    f_word_results.push(Vec::new());
+                }
+
+                f_cls_angles.push(std::mem::take(&mut angles[idx]));
+                f_cls_scores.push(image_cls_scores[idx]);
+            }
+
+            outputs.push(RapidOcrOutput {
+                boxes: f_boxes,
+                txts: f_txts,
+                scores: f_scores,
+                word_results: f_word_results,
+                cls_angles: f_cls_angles,
+                cls_scores: f_cls_scores,
+                elapse_det,
+                elapse_rec: rec_res.elapse,
+            });
+        }
+
+        Ok(outputs)
+    }
+
+    /// Build a fresh, independently-owned pipeline from this one's configs
+    /// (each MNN session is reloaded, not shared), so a worker thread in
+    /// `run_batch` gets exclusive `&mut` access to its own detector /
+    /// recognizer / classifier instead of contending over `self`'s.
+    fn clone_pipeline(&self) -> Result<Self, EngineError> {
+        let det = TextDetector::new(self.det.cfg.clone())?;
+        let rec = TextRecognizer::new(self.rec.cfg.clone())?;
+        let cls = match &self.cls {
+            Some(c) => Some(TextClassifier::new(c.cfg.clone())?),
+            None => None,
+        };
+        Ok(Self {
+            det,
+            rec,
+            cls,
+            global: self.global.clone(),
+            cal_rec_boxes: CalRecBoxes::new(),
+        })
+    }
+
+    /// Run OCR over many images, splitting `image_paths` into
+    /// `GlobalConfig::num_threads` contiguous chunks and processing each
+    /// chunk on its own thread with its own cloned pipeline (MNN sessions
+    /// aren't `Sync`, so threads can't share `self.det`/`self.rec`/`self.cls`
+    /// directly). Falls back to running sequentially on `self` when there's
+    /// only one worker or one image, so the common case pays no cloning cost.
+    pub fn run_batch<P: AsRef<Path> + Sync>(
+        &mut self,
+        image_paths: &[P],
+    ) -> Vec<Result<RapidOcrOutput, EngineError>> {
+        let n = image_paths.len();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let num_workers = self.global.num_threads.max(1).min(n);
+        if num_workers <= 1 {
+            return image_paths.iter().map(|p| self.run(p)).collect();
+        }
+
+        let chunk_size = n.div_ceil(num_workers);
+        let chunks: Vec<&[P]> = image_paths.chunks(chunk_size).collect();
+
+        let mut extra_pipelines: Vec<Result<Self, EngineError>> = Vec::new();
+        for _ in 1..chunks.len() {
+            extra_pipelines.push(self.clone_pipeline());
+        }
+
+        let mut results: Vec<Vec<Result<RapidOcrOutput, EngineError>>> =
+            (0..chunks.len()).map(|_| Vec::new()).collect();
+
+        std::thread::scope(|scope| {
+            let mut handles = Vec::new();
+            let mut extra_iter = extra_pipelines.into_iter();
+
+            for (idx, chunk) in chunks.iter().enumerate() {
+                if idx == 0 {
+                    results[0] = chunk.iter().map(|p| self.run(p)).collect();
+                    continue;
+                }
+
+                let pipeline = extra_iter.next().unwrap();
+                let chunk = *chunk;
+                handles.push((
+                    idx,
+                    scope.spawn(move || {
+                        let mut pipeline = pipeline?;
+                        Ok::<_, EngineError>(
+                            chunk.iter().map(|p| pipeline.run(p)).collect::<Vec<_>>(),
+                        )
+                    }),
+                ));
+            }
+
+            for (idx, handle) in handles {
+                results[idx] = match handle.join() {
+                    Ok(Ok(chunk_results)) => chunk_results,
+                    Ok(Err(e)) => chunks[idx]
+                        .iter()
+                        .map(|_| Err(EngineError::OutputError(format!("worker pipeline failed: {e}"))))
+                        .collect(),
+                    Err(_) => chunks[idx]
+                        .iter()
+                        .map(|_| Err(EngineError::OutputError("worker thread panicked".to_string())))
+                        .collect(),
+                };
+            }
+        });
+
+        results.into_iter().flatten().collect()
+    }
+}
+
+/// Reorder `boxes` into top-to-bottom, left-to-right reading order in place.
+/// Sorts quads by y-center, then greedily groups consecutive boxes into
+/// "lines" while a box's y-center stays within half the median box height of
+/// the line's first box, starting a new line once it doesn't; each line is
+/// then sorted by x-center. Used by `run_on_mat`/`run_on_mat_timed` when
+/// `GlobalConfig::sort_boxes` is set.
+fn sort_boxes_reading_order(boxes: &mut Vec<Quad>) {
+    let n = boxes.len();
+    if n < 2 {
+        return;
+    }
+
+    let centroid = |q: &Quad| -> (f32, f32) {
+        let sx: f32 = q.iter().map(|p| p.x).sum();
+        let sy: f32 = q.iter().map(|p| p.y).sum();
+        (sx / 4.0, sy / 4.0)
+    };
+    let height = |q: &Quad| -> f32 {
+        let y_min = q.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+        let y_max = q.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+        y_max - y_min
+    };
+
+    let centroids: Vec<(f32, f32)> = boxes.iter().map(centroid).collect();
+    let mut heights: Vec<f32> = boxes.iter().map(height).collect();
+    heights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median_height = heights[heights.len() / 2];
+    let threshold = (median_height * 0.5).max(1.0);
+
+    let mut indices: Vec<usize> = (0..n).collect();
+    indices.sort_by(|&a, &b| centroids[a].1.partial_cmp(&centroids[b].1).unwrap());
+
+    let mut lines: Vec<Vec<usize>> = Vec::new();
+    let mut current_line: Vec<usize> = Vec::new();
+    let mut line_y = 0.0f32;
+
+    for idx in indices {
+        let y = centroids[idx].1;
+        if current_line.is_empty() {
+            line_y = y;
+            current_line.push(idx);
+        } else if (y - line_y).abs() <= threshold {
+            current_line.push(idx);
+        } else {
+            lines.push(std::mem::take(&mut current_line));
+            line_y = y;
+            current_line.push(idx);
+        }
+    }
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    let mut ordered: Vec<usize> = Vec::with_capacity(n);
+    for mut line in lines {
+        line.sort_by(|&a, &b| centroids[a].0.partial_cmp(&centroids[b].0).unwrap());
+        ordered.extend(line);
+    }
+
+    let originals = boxes.clone();
+    *boxes = ordered.into_iter().map(|i| originals[i]).collect();
 }
 
@@ -0,0 +1,139 @@
+//! Shared Ramer–Douglas–Peucker polyline simplification, matching OpenCV's
+//! `approxPolyDP`: find the point farthest (perpendicular distance) from the
+//! chord through a segment's endpoints; if that distance exceeds `epsilon`,
+//! keep the point and recurse on both halves, otherwise collapse the whole
+//! segment down to just its endpoints. `simplify_closed` treats the input as
+//! a loop (splitting it into two open arcs at its farthest-apart point pair
+//! first) rather than a polyline with fixed first/last points.
+//!
+//! Operates on plain `(f64, f64)` tuples so `contours::approx_poly_dp`
+//! (integer `Contour` points), `image_impl`'s internal det-pipeline
+//! `approx_poly_dp` (`Point2f`), and `postprocess::approx_poly_dp` (also
+//! `Point2f`, over a `Contour`) can each convert to/from their own point type
+//! at the edges instead of each re-implementing the recursion.
+
+/// Perpendicular distance from `p` to the infinite line through `a` and `b`
+/// (not the segment — a point beyond `a`/`b` still measures against the full
+/// line, same as OpenCV's `approxPolyDP`), falling back to point-to-point
+/// distance when `a == b` so a degenerate chord doesn't divide by zero.
+pub(crate) fn perpendicular_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len < 1e-9 {
+        return ((p.0 - a.0).powi(2) + (p.1 - a.1).powi(2)).sqrt();
+    }
+    ((p.0 - a.0) * dy - (p.1 - a.1) * dx).abs() / len
+}
+
+/// Recursive Douglas-Peucker reduction of the open polyline `points`:
+/// finds the point farthest from the chord joining the first and last
+/// points, keeps it (and recurses on both halves) if that distance exceeds
+/// `epsilon`, otherwise discards every point in between. `points` must have
+/// at least 2 elements; the endpoints are always kept.
+pub(crate) fn simplify_open(points: &[(f64, f64)], epsilon: f64) -> Vec<(f64, f64)> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let mut max_dist = 0.0;
+    let mut max_idx = 0;
+    for (i, &p) in points.iter().enumerate().take(points.len() - 1).skip(1) {
+        let dist = perpendicular_distance(p, first, last);
+        if dist > max_dist {
+            max_dist = dist;
+            max_idx = i;
+        }
+    }
+
+    if max_dist <= epsilon {
+        return vec![first, last];
+    }
+
+    let mut left = simplify_open(&points[..=max_idx], epsilon);
+    let right = simplify_open(&points[max_idx..], epsilon);
+    left.pop(); // avoid duplicating the shared split point
+    left.extend(right);
+    left
+}
+
+/// Indices of the two mutually farthest-apart points in `points`, used to
+/// split a closed polygon before running `simplify_open` on each resulting
+/// arc — so the arbitrary point a border tracer happened to start at
+/// doesn't bias the simplification.
+fn farthest_pair(points: &[(f64, f64)]) -> (usize, usize) {
+    let mut best = (0, 0);
+    let mut best_dist = -1.0;
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            let (dx, dy) = (points[i].0 - points[j].0, points[i].1 - points[j].1);
+            let dist = dx * dx + dy * dy;
+            if dist > best_dist {
+                best_dist = dist;
+                best = (i, j);
+            }
+        }
+    }
+    best
+}
+
+/// Simplify a closed polygon: split it at its two most distant vertices,
+/// `simplify_open` each resulting arc independently, then stitch the
+/// results back together, dropping the one point shared at each splice —
+/// the same trick OpenCV's `approxPolyDP` uses for `closed=true`.
+pub(crate) fn simplify_closed(points: &[(f64, f64)], epsilon: f64) -> Vec<(f64, f64)> {
+    let (i, j) = farthest_pair(points);
+    let (lo, hi) = if i < j { (i, j) } else { (j, i) };
+
+    // Arc from lo to hi, and the wraparound arc from hi back to lo.
+    let arc1: Vec<(f64, f64)> = points[lo..=hi].to_vec();
+    let mut arc2: Vec<(f64, f64)> = points[hi..].to_vec();
+    arc2.extend_from_slice(&points[..=lo]);
+
+    let mut simplified1 = simplify_open(&arc1, epsilon);
+    let simplified2 = simplify_open(&arc2, epsilon);
+
+    simplified1.pop(); // drop the point shared with simplified2's first point
+    simplified1.extend(simplified2);
+    simplified1.pop(); // drop the point shared with simplified1's first point
+    simplified1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simplify_open_collapses_near_straight_line() {
+        let points = vec![(0.0, 0.0), (1.0, 0.01), (2.0, -0.01), (3.0, 0.0)];
+        let simplified = simplify_open(&points, 0.5);
+        assert_eq!(simplified, vec![(0.0, 0.0), (3.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_simplify_open_keeps_a_spike_above_epsilon() {
+        let points = vec![(0.0, 0.0), (1.0, 0.0), (2.0, 5.0), (3.0, 0.0), (4.0, 0.0)];
+        let simplified = simplify_open(&points, 0.1);
+        assert!(simplified.contains(&(2.0, 5.0)));
+        assert_eq!(simplified.first(), Some(&(0.0, 0.0)));
+        assert_eq!(simplified.last(), Some(&(4.0, 0.0)));
+    }
+
+    #[test]
+    fn test_simplify_closed_collapses_noisy_square_to_quad() {
+        // A roughly-square loop with a couple of near-collinear extra points
+        // along each edge, as a hull tracer might leave behind.
+        let points = vec![
+            (0.0, 0.0),
+            (5.0, 0.1),
+            (10.0, 0.0),
+            (10.0, 5.0),
+            (10.0, 10.0),
+            (5.0, 9.9),
+            (0.0, 10.0),
+            (0.0, 5.0),
+        ];
+        let simplified = simplify_closed(&points, 1.0);
+        assert_eq!(simplified.len(), 4);
+    }
+}
@@ -2,7 +2,7 @@ use std::io::{BufRead, BufReader};
 use std::fs::File;
 use std::time::Instant;
 
-use ndarray::{Array3, Array4, Ix3};
+use ndarray::{Array2, Array3, Array4, Ix3};
 
 #[cfg(feature = "use-opencv")]
 use opencv::{core, imgproc, prelude::*};
@@ -16,12 +16,135 @@ use crate::image_impl::{Mat, Size, INTER_LINEAR};
 use crate::engine::{EngineError, MnnSession};
 use crate::types::RecConfig;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WordType {
     Cn,
     EnNum,
 }
 
+/// Selects how `CtcDecoder` turns a model's per-timestep class
+/// probabilities into text. `Greedy` (the default) takes the per-frame
+/// argmax and collapses repeats/blanks; `BeamSearch` keeps the top `width`
+/// candidate prefixes at every timestep, which costs more compute but
+/// recovers from locally-ambiguous frames greedy decoding would commit to
+/// too early.
+#[derive(Clone, Debug, Default)]
+pub enum DecodeMode {
+    #[default]
+    Greedy,
+    BeamSearch {
+        width: usize,
+        lm: Option<NgramLm>,
+    },
+}
+
+/// Describes the label layout a recognition model's dictionary was trained
+/// with, so `CtcDecoder` doesn't have to assume PaddleOCR's usual "blank
+/// first, space last" convention. Most PP-OCR models want the default.
+#[derive(Clone, Debug)]
+pub struct CtcLabelConfig {
+    /// Index the `blank` token is inserted at, relative to the loaded
+    /// dictionary before `blank`/space are added. `0` (the default) prepends
+    /// it, matching PaddleOCR's convention; pass `usize::MAX` to append it as
+    /// the last class instead, after the space entry when `use_space_char`
+    /// adds one. The dictionary's entry count places it between the
+    /// dictionary and that space entry.
+    pub blank_index: usize,
+    /// Whether to append a trailing space entry to the vocabulary, used as
+    /// the word-boundary marker during beam search LM rescoring. Models
+    /// trained without a space token should set this to `false`.
+    pub use_space_char: bool,
+    /// Extra vocabulary indices (besides `blank_index`) that greedy decoding
+    /// should drop from the output, for dictionaries with additional
+    /// control tokens beyond blank.
+    pub ignore_indices: Vec<usize>,
+}
+
+impl Default for CtcLabelConfig {
+    fn default() -> Self {
+        Self {
+            blank_index: 0,
+            use_space_char: true,
+            ignore_indices: Vec::new(),
+        }
+    }
+}
+
+/// A word-level n-gram language model used to rescore CTC beam search
+/// candidates at word boundaries. Loaded from a plain-text file of
+/// `word<TAB>log10_prob` lines (the format ARPA/KenLM tools export their
+/// unigram table as); words absent from the table fall back to
+/// `unknown_log_prob`.
+#[derive(Clone, Debug)]
+pub struct NgramLm {
+    word_log_probs: std::collections::HashMap<String, f32>,
+    unknown_log_prob: f32,
+    /// Exponent applied to the LM probability when rescoring a beam
+    /// (`score *= lm.prob(word).powf(alpha)`); higher values trust the LM
+    /// more relative to the acoustic model.
+    pub alpha: f32,
+    /// Flat bonus added to a beam's score for every completed word, to
+    /// counteract the LM's preference for fewer, longer words.
+    pub beta: f32,
+}
+
+impl NgramLm {
+    /// Load a unigram table from `path`. Lines that don't parse as
+    /// `word<TAB>log10_prob` are skipped rather than failing the whole
+    /// load, since stray blank lines/comments are common in exported LM
+    /// tables.
+    pub fn load(path: &std::path::Path, alpha: f32, beta: f32) -> Result<Self, EngineError> {
+        let file = File::open(path)
+            .map_err(|e| EngineError::Preprocess(format!("failed to open n-gram LM file: {e}")))?;
+        let reader = BufReader::new(file);
+
+        let mut word_log_probs = std::collections::HashMap::new();
+        for line in reader.lines() {
+            let line = line
+                .map_err(|e| EngineError::Preprocess(format!("failed to read n-gram LM file: {e}")))?;
+            let mut parts = line.splitn(2, '\t');
+            let (Some(word), Some(log_prob)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if let Ok(log_prob) = log_prob.trim().parse::<f32>() {
+                word_log_probs.insert(word.to_string(), log_prob);
+            }
+        }
+
+        Ok(Self {
+            word_log_probs,
+            unknown_log_prob: -10.0,
+            alpha,
+            beta,
+        })
+    }
+
+    /// The model's probability for `word`, in linear (not log) space.
+    pub fn prob(&self, word: &str) -> f32 {
+        let log_prob = self
+            .word_log_probs
+            .get(word)
+            .copied()
+            .unwrap_or(self.unknown_log_prob);
+        10f32.powf(log_prob)
+    }
+
+    /// Split the rescoring applied to a beam that just completed `word` into
+    /// its multiplicative and additive parts: `prob(word).powf(alpha)` scales
+    /// the beam's probability mass by how plausible the LM finds the word,
+    /// and `beta` is returned separately so callers add it to the score
+    /// rather than folding it into that multiplicative factor — doing the
+    /// latter lets a large `beta` swamp the factor's penalty on an
+    /// implausible word instead of just discounting the LM's bias toward
+    /// fewer, longer words.
+    fn word_boost(&self, word: &str) -> (f32, f32) {
+        (self.prob(word).powf(self.alpha), self.beta)
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct WordInfo {
     pub words: Vec<Vec<String>>,
@@ -36,11 +159,43 @@ pub struct TextRecOutput {
     pub txts: Vec<String>,
     pub scores: Vec<f32>,
     pub word_infos: Vec<Option<WordInfo>>,
+    /// Per-timestep class probabilities for each input, one `T x C` matrix
+    /// in the same order as `txts`, when `run` was called with
+    /// `return_logits: true`. Empty otherwise, so callers who don't ask for
+    /// this skip the extra clone of the model's output tensor entirely.
+    pub raw_logits: Vec<Array2<f32>>,
     pub elapse: f64,
 }
 
+/// Per-prefix state kept by `CtcDecoder::decode_beam_search`: the
+/// probability mass of paths that collapse to this prefix and currently
+/// end in a blank (`p_b`) versus end in the prefix's last non-blank
+/// character (`p_nb`). Kept separate because a repeated character only
+/// extends the prefix if a blank came between the two occurrences.
+#[derive(Clone, Copy, Debug, Default)]
+struct BeamProb {
+    p_b: f32,
+    p_nb: f32,
+}
+
+impl BeamProb {
+    fn total(&self) -> f32 {
+        self.p_b + self.p_nb
+    }
+}
+
+/// Common prefix shared by most PaddleOCR-style character dictionaries:
+/// digits followed by lowercase ASCII letters. A compressed, language-specific
+/// `rec_keys_path` can be delta-compressed against this instead of carrying
+/// its own copy of these entries; see [`crate::inflate::inflate_raw_with_dict`].
+const BASE_CHAR_DICT: &str = "0123456789abcdefghijklmnopqrstuvwxyz\n";
+
 struct CtcDecoder {
     chars: Vec<String>,
+    decode_mode: DecodeMode,
+    blank_index: usize,
+    ignore_indices: Vec<usize>,
+    space_index: Option<usize>,
 }
 
 impl CtcDecoder {
@@ -55,17 +210,30 @@ impl CtcDecoder {
 
         if chars.is_none() {
             if let Some(path) = &cfg.rec_keys_path {
-                let file = File::open(path).map_err(|e| {
-                    EngineError::Preprocess(format!("failed to open rec_keys_path: {e}"))
-                })?;
-                let reader = BufReader::new(file);
-                let mut list = Vec::new();
-                for line in reader.lines() {
-                    let l = line.map_err(|e| {
-                        EngineError::Preprocess(format!("failed to read rec_keys_path: {e}"))
+                let list = if let Some(inflated) =
+                    crate::inflate::maybe_inflate_file_with_dict(path, Some(BASE_CHAR_DICT.as_bytes()))?
+                {
+                    BufReader::new(inflated.as_slice())
+                        .lines()
+                        .map(|line| {
+                            line.map_err(|e| {
+                                EngineError::Preprocess(format!("failed to read rec_keys_path: {e}"))
+                            })
+                        })
+                        .collect::<Result<Vec<String>, EngineError>>()?
+                } else {
+                    let file = File::open(path).map_err(|e| {
+                        EngineError::Preprocess(format!("failed to open rec_keys_path: {e}"))
                     })?;
-                    list.push(l);
-                }
+                    BufReader::new(file)
+                        .lines()
+                        .map(|line| {
+                            line.map_err(|e| {
+                                EngineError::Preprocess(format!("failed to read rec_keys_path: {e}"))
+                            })
+                        })
+                        .collect::<Result<Vec<String>, EngineError>>()?
+                };
                 chars = Some(list);
             }
         }
@@ -74,10 +242,42 @@ impl CtcDecoder {
             EngineError::Preprocess("no character list found for recognizer".to_string())
         })?;
 
-        character_list.push(" ".to_string());
-        character_list.insert(0, "blank".to_string());
+        let space_index = if cfg.ctc_label.use_space_char {
+            character_list.push(" ".to_string());
+            Some(character_list.len() - 1)
+        } else {
+            None
+        };
+
+        let blank_index = cfg.ctc_label.blank_index.min(character_list.len());
+        character_list.insert(blank_index, "blank".to_string());
+        // Inserting blank shifts every entry at or after it, including space.
+        let space_index = space_index.map(|idx| if blank_index <= idx { idx + 1 } else { idx });
+
+        Ok(Self {
+            chars: character_list,
+            decode_mode: cfg.decode_mode.clone(),
+            blank_index,
+            ignore_indices: cfg.ctc_label.ignore_indices.clone(),
+            space_index,
+        })
+    }
 
-        Ok(Self { chars: character_list })
+    /// Check that the model's output vocabulary size matches the loaded
+    /// character dictionary (plus the `blank`/space entries we prepend and
+    /// append), so a mismatched language/model/dictionary combination fails
+    /// fast with a clear error instead of silently producing garbled text.
+    fn validate_vocab_size(&self, output_vocab_size: usize) -> Result<(), EngineError> {
+        if output_vocab_size != self.chars.len() {
+            return Err(EngineError::Preprocess(format!(
+                "recognizer vocabulary mismatch: model outputs {} classes but the \
+                 loaded dictionary has {} entries (including blank/space) \
+                 — check that rec_keys_path matches this model's language",
+                output_vocab_size,
+                self.chars.len()
+            )));
+        }
+        Ok(())
     }
 
     fn decode(
@@ -86,8 +286,9 @@ impl CtcDecoder {
         return_word_box: bool,
         wh_ratio_list: &[f32],
         max_wh_ratio: f32,
-    ) -> (Vec<(String, f32)>, Vec<WordInfo>) {
+    ) -> Result<(Vec<(String, f32)>, Vec<WordInfo>), EngineError> {
         let (n, t, c) = preds.dim();
+        self.validate_vocab_size(c)?;
         let mut line_results = Vec::with_capacity(n);
         let mut word_infos = Vec::with_capacity(if return_word_box { n } else { 0 });
 
@@ -113,52 +314,57 @@ impl CtcDecoder {
                 token_probs.push(best_val);
             }
 
-            let ignored_tokens = self.get_ignored_tokens();
-            let mut selection = vec![true; token_indices.len()];
+            let (text, selection, mean_score) = match &self.decode_mode {
+                DecodeMode::Greedy => {
+                    let ignored_tokens = self.get_ignored_tokens();
+                    let mut selection = vec![true; token_indices.len()];
+
+                    if !token_indices.is_empty() {
+                        for i in 1..token_indices.len() {
+                            if token_indices[i] == token_indices[i - 1] {
+                                selection[i] = false;
+                            }
+                        }
+                    }
 
-            if !token_indices.is_empty() {
-                for i in 1..token_indices.len() {
-                    if token_indices[i] == token_indices[i - 1] {
-                        selection[i] = false;
+                    for &ignored in &ignored_tokens {
+                        for (i, sel) in selection.iter_mut().enumerate() {
+                            if token_indices[i] == ignored {
+                                *sel = false;
+                            }
+                        }
                     }
-                }
-            }
 
-            for &ignored in &ignored_tokens {
-                for (i, sel) in selection.iter_mut().enumerate() {
-                    if token_indices[i] == ignored {
-                        *sel = false;
+                    // Pre-allocate chars vector
+                    let est_size = selection.iter().filter(|&&s| s).count().max(1);
+                    let mut chars = Vec::with_capacity(est_size);
+                    for (i, &sel) in selection.iter().enumerate() {
+                        if sel {
+                            if let Some(ch) = self.chars.get(token_indices[i]) {
+                                chars.push(ch.as_str());
+                            }
+                        }
                     }
-                }
-            }
 
-            // Pre-allocate conf_list with estimated size
-            let est_size = selection.iter().filter(|&&s| s).count().max(1);
-            let mut conf_list = Vec::with_capacity(est_size);
-            for (i, &sel) in selection.iter().enumerate() {
-                if sel {
-                    let mut v = token_probs[i];
-                    v = (v * 1e5).round() / 1e5;
-                    conf_list.push(v);
+                    let conf_list = self.conf_list_for_selection(&token_probs, &selection);
+                    let mean_score: f32 = conf_list.iter().copied().sum::<f32>() / (conf_list.len() as f32);
+                    (chars.concat(), selection, mean_score)
                 }
-            }
-
-            if conf_list.is_empty() {
-                conf_list.push(0.0);
-            }
-
-            // Pre-allocate chars vector
-            let mut chars = Vec::with_capacity(est_size);
-            for (i, &sel) in selection.iter().enumerate() {
-                if sel {
-                    if let Some(ch) = self.chars.get(token_indices[i]) {
-                        chars.push(ch.as_str());
+                DecodeMode::BeamSearch { width, lm } => {
+                    let batch_view = preds.index_axis(ndarray::Axis(0), b);
+                    let (text, score, cols) =
+                        self.decode_beam_search(batch_view, &token_indices, *width, lm.as_ref());
+                    let mut selection = vec![false; token_indices.len()];
+                    for &col in &cols {
+                        if col < selection.len() {
+                            selection[col] = true;
+                        }
                     }
+                    (text, selection, score)
                 }
-            }
+            };
 
-            let text = chars.concat();
-            let mean_score: f32 = conf_list.iter().copied().sum::<f32>() / (conf_list.len() as f32);
+            let conf_list = self.conf_list_for_selection(&token_probs, &selection);
 
             line_results.push((text.clone(), mean_score));
 
@@ -176,11 +382,142 @@ impl CtcDecoder {
             }
         }
 
-        (line_results, word_infos)
+        Ok((line_results, word_infos))
     }
 
     fn get_ignored_tokens(&self) -> Vec<usize> {
-        vec![0]
+        let mut tokens = vec![self.blank_index];
+        tokens.extend(self.ignore_indices.iter().copied());
+        tokens
+    }
+
+    /// Confidence per selected (non-collapsed, non-blank) timestep, rounded
+    /// the same way greedy decoding always has; falls back to `[0.0]` when
+    /// nothing was selected so callers can divide by `len()` safely.
+    fn conf_list_for_selection(&self, token_probs: &[f32], selection: &[bool]) -> Vec<f32> {
+        let mut conf_list: Vec<f32> = selection
+            .iter()
+            .enumerate()
+            .filter(|(_, &sel)| sel)
+            .map(|(i, _)| (token_probs[i] * 1e5).round() / 1e5)
+            .collect();
+        if conf_list.is_empty() {
+            conf_list.push(0.0);
+        }
+        conf_list
+    }
+
+    /// Prefix beam search over one batch item's `T x C` frame probabilities
+    /// (column `blank_index` = blank). Returns the winning prefix's text, its
+    /// `p_b + p_nb` confidence, and the timesteps that produced each of its
+    /// characters (recovered by walking `frame_argmax` and greedily
+    /// matching it against the winning prefix, since the beam's own argmax
+    /// path is overwhelmingly likely to contain that prefix as a
+    /// subsequence).
+    fn decode_beam_search(
+        &self,
+        batch_view: ndarray::ArrayView2<f32>,
+        frame_argmax: &[usize],
+        width: usize,
+        lm: Option<&NgramLm>,
+    ) -> (String, f32, Vec<usize>) {
+        let (t_len, c_len) = batch_view.dim();
+
+        let mut beams: std::collections::HashMap<Vec<usize>, BeamProb> = std::collections::HashMap::new();
+        beams.insert(Vec::new(), BeamProb { p_b: 1.0, p_nb: 0.0 });
+
+        for ti in 0..t_len {
+            let row = batch_view.index_axis(ndarray::Axis(0), ti);
+            let mut next: std::collections::HashMap<Vec<usize>, BeamProb> = std::collections::HashMap::new();
+
+            for (prefix, probs) in &beams {
+                let p_blank = row[self.blank_index];
+                next.entry(prefix.clone()).or_default().p_b += (probs.p_b + probs.p_nb) * p_blank;
+
+                let last_char = prefix.last().copied();
+                for ci in 0..c_len {
+                    if ci == self.blank_index {
+                        continue;
+                    }
+                    let pc = row[ci];
+                    if Some(ci) == last_char {
+                        next.entry(prefix.clone()).or_default().p_nb += probs.p_nb * pc;
+
+                        let mut extended = prefix.clone();
+                        extended.push(ci);
+                        next.entry(extended).or_default().p_nb += probs.p_b * pc;
+                    } else {
+                        let mut extended = prefix.clone();
+                        extended.push(ci);
+                        next.entry(extended).or_default().p_nb += (probs.p_b + probs.p_nb) * pc;
+                    }
+                }
+            }
+
+            if let (Some(lm), Some(space_idx)) = (lm, self.space_index) {
+                for (prefix, probs) in next.iter_mut() {
+                    if prefix.last().copied() != Some(space_idx) {
+                        continue;
+                    }
+                    let word = self.prefix_to_text(&prefix[..prefix.len() - 1]);
+                    if word.is_empty() {
+                        continue;
+                    }
+                    // `beta` is a flat per-word bonus, not part of the LM
+                    // factor (see `word_boost`'s doc comment) — split it
+                    // across `p_b`/`p_nb` so it lands exactly once in their
+                    // sum, `BeamProb::total`.
+                    let (lm_factor, beta) = lm.word_boost(&word);
+                    probs.p_b = probs.p_b * lm_factor + beta / 2.0;
+                    probs.p_nb = probs.p_nb * lm_factor + beta / 2.0;
+                }
+            }
+
+            let mut entries: Vec<(Vec<usize>, BeamProb)> = next.into_iter().collect();
+            entries.sort_by(|a, b| b.1.total().partial_cmp(&a.1.total()).unwrap_or(std::cmp::Ordering::Equal));
+            entries.truncate(width.max(1));
+            beams = entries.into_iter().collect();
+        }
+
+        let best = beams
+            .into_iter()
+            .max_by(|a, b| a.1.total().partial_cmp(&b.1.total()).unwrap_or(std::cmp::Ordering::Equal));
+
+        match best {
+            Some((prefix, probs)) => {
+                let text = self.prefix_to_text(&prefix);
+                let cols = self.align_columns(frame_argmax, &prefix);
+                (text, probs.total(), cols)
+            }
+            None => (String::new(), 0.0, Vec::new()),
+        }
+    }
+
+    /// Render a sequence of non-blank char indices as text.
+    fn prefix_to_text(&self, prefix: &[usize]) -> String {
+        prefix
+            .iter()
+            .filter_map(|&idx| self.chars.get(idx))
+            .map(|s| s.as_str())
+            .collect()
+    }
+
+    /// Recover the timestep each character of `prefix` most likely came
+    /// from by walking the per-frame argmax left to right and greedily
+    /// matching it against `prefix` in order.
+    fn align_columns(&self, frame_argmax: &[usize], prefix: &[usize]) -> Vec<usize> {
+        let mut cols = Vec::with_capacity(prefix.len());
+        let mut pi = 0usize;
+        for (t, &idx) in frame_argmax.iter().enumerate() {
+            if pi >= prefix.len() {
+                break;
+            }
+            if idx == prefix[pi] {
+                cols.push(t);
+                pi += 1;
+            }
+        }
+        cols
     }
 
     fn get_word_info(&self, text: &str, selection: &[bool]) -> WordInfo {
@@ -291,7 +628,12 @@ impl TextRecognizer {
         Ok(Self { cfg, session, decoder })
     }
 
-    pub fn run(&mut self, imgs: &[Mat], return_word_box: bool) -> Result<TextRecOutput, EngineError> {
+    pub fn run(
+        &mut self,
+        imgs: &[Mat],
+        return_word_box: bool,
+        return_logits: bool,
+    ) -> Result<TextRecOutput, EngineError> {
         let start = Instant::now();
 
         if imgs.is_empty() {
@@ -300,6 +642,7 @@ impl TextRecognizer {
                 txts: Vec::new(),
                 scores: Vec::new(),
                 word_infos: Vec::new(),
+                raw_logits: Vec::new(),
                 elapse: 0.0,
             });
         }
@@ -321,6 +664,11 @@ impl TextRecognizer {
 
         let mut all_texts: Vec<(String, f32)> = vec![(String::new(), 0.0); img_num];
         let mut all_word_infos: Vec<Option<WordInfo>> = vec![None; img_num];
+        let mut all_logits: Vec<Array2<f32>> = if return_logits {
+            vec![Array2::<f32>::zeros((0, 0)); img_num]
+        } else {
+            Vec::new()
+        };
 
         let (img_c, img_h, img_w) = (
             self.cfg.rec_img_shape[0] as usize,
@@ -344,11 +692,8 @@ impl TextRecognizer {
                 wh_ratio_list.push(wh_ratio);
             }
 
-            let mut norm_batch: Vec<Array3<f32>> = Vec::with_capacity(end - beg);
-            for &idx in &indices[beg..end] {
-                let norm = self.resize_norm_img(&img_list[idx], img_c, img_h, img_w, max_wh_ratio)?;
-                norm_batch.push(norm);
-            }
+            let norm_batch: Vec<Array3<f32>> =
+                self.resize_norm_batch(&img_list, &indices[beg..end], img_c, img_h, img_w, max_wh_ratio)?;
 
             let n = norm_batch.len();
             // Use calculated batch_img_width based on max_wh_ratio, not configured img_w
@@ -363,8 +708,14 @@ impl TextRecognizer {
                 .into_dimensionality::<Ix3>()
                 .map_err(|_| EngineError::InvalidInputShape)?;
 
+            if return_logits {
+                for (local_idx, &idx) in indices[beg..end].iter().enumerate() {
+                    all_logits[idx] = preds.index_axis(ndarray::Axis(0), local_idx).to_owned();
+                }
+            }
+
             let (line_results, batch_word_infos) =
-                self.decoder.decode(preds, return_word_box, &wh_ratio_list, max_wh_ratio);
+                self.decoder.decode(preds, return_word_box, &wh_ratio_list, max_wh_ratio)?;
 
             if return_word_box {
                 for (local_idx, ((text, score), info)) in line_results
@@ -394,123 +745,194 @@ impl TextRecognizer {
             txts,
             scores,
             word_infos: all_word_infos,
+            raw_logits: all_logits,
             elapse,
         })
     }
 
-    #[cfg(feature = "use-opencv")]
     fn resize_norm_img(
         &self,
         img: &Mat,
         img_c: usize,
         img_h: usize,
-        _img_w: usize,
+        img_w: usize,
         max_wh_ratio: f32,
     ) -> Result<Array3<f32>, EngineError> {
-        let img_width = (img_h as f32 * max_wh_ratio).round() as i32;
-
-        let h = img.rows();
-        let w = img.cols();
-        if h <= 0 || w <= 0 {
-            return Err(EngineError::Preprocess("invalid image size".to_string()));
-        }
-
-        let ratio = w as f32 / h as f32;
-        let resized_w = if ((img_h as f32) * ratio).ceil() as i32 > img_width {
-            img_width
-        } else {
-            ((img_h as f32) * ratio).ceil() as i32
-        };
-
-        let mut resized = Mat::default();
-        imgproc::resize(
+        crnn_resize_norm_img(
             img,
-            &mut resized,
-            core::Size::new(resized_w, img_h as i32),
-            0.0,
-            0.0,
-            imgproc::INTER_LINEAR,
-        )?;
-
-        let size = resized.size()?;
-        let h2 = size.height as usize;
-        let w2 = size.width as usize;
-
-        // Create zero-padded array like Python: padding_im = np.zeros((img_channel, img_height, img_width))
-        // IMPORTANT: Use calculated img_width (from max_wh_ratio), not configured img_w!
-        let mut out = Array3::<f32>::zeros((img_c, img_h, img_width as usize));
-
-        // Only fill the resized portion: padding_im[:, :, 0:resized_w] = resized_image
-        // The rest remains zeros (padding on the right)
-        for y in 0..h2 {
-            for x in 0..w2.min(img_width as usize) {  // Ensure we don't exceed img_width
-                let pix = resized.at_2d::<core::Vec3b>(y as i32, x as i32)?;
-                let b = pix[0] as f32 / 255.0;
-                let g = pix[1] as f32 / 255.0;
-                let r = pix[2] as f32 / 255.0;
-
-                out[[0, y, x]] = (b - 0.5) / 0.5;
-                out[[1, y, x]] = (g - 0.5) / 0.5;
-                out[[2, y, x]] = (r - 0.5) / 0.5;
-            }
-        }
-
-        Ok(out)
+            img_c,
+            img_h,
+            img_w,
+            max_wh_ratio,
+            self.cfg.input_color,
+            self.cfg.background,
+        )
     }
 
-    #[cfg(not(feature = "use-opencv"))]
-    fn resize_norm_img(
+    /// Resize-and-normalize every image in `indices` (into `img_list`), in
+    /// parallel across the batch dimension when built with the `parallel`
+    /// feature (capped at `RecConfig::preprocess_threads` workers, or
+    /// rayon's global pool when `0`). Results come back in `indices` order.
+    fn resize_norm_batch(
         &self,
-        img: &Mat,
+        img_list: &[Mat],
+        indices: &[usize],
         img_c: usize,
         img_h: usize,
-        _img_w: usize,
+        img_w: usize,
         max_wh_ratio: f32,
-    ) -> Result<Array3<f32>, EngineError> {
-        let img_width = (img_h as f32 * max_wh_ratio).round() as i32;
+    ) -> Result<Vec<Array3<f32>>, EngineError> {
+        let norm = |&idx: &usize| self.resize_norm_img(&img_list[idx], img_c, img_h, img_w, max_wh_ratio);
+
+        #[cfg(feature = "parallel")]
+        {
+            if self.cfg.preprocess_threads > 0 {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(self.cfg.preprocess_threads)
+                    .build()
+                    .map_err(|e| EngineError::Preprocess(e.to_string()))?;
+                pool.install(|| indices.par_iter().map(norm).collect())
+            } else {
+                indices.par_iter().map(norm).collect()
+            }
+        }
 
-        let h = img.rows();
-        let w = img.cols();
-        if h <= 0 || w <= 0 {
-            return Err(EngineError::Preprocess("invalid image size".to_string()));
+        #[cfg(not(feature = "parallel"))]
+        {
+            indices.iter().map(norm).collect()
         }
+    }
+}
 
-        let ratio = w as f32 / h as f32;
-        let resized_w = if ((img_h as f32) * ratio).ceil() as i32 > img_width {
-            img_width
-        } else {
-            ((img_h as f32) * ratio).ceil() as i32
-        };
+/// CRNN-style resize-and-normalize step shared by the recognizer.
+///
+/// Resizes `img` to `img_h` while keeping its aspect ratio (capped at the
+/// width implied by `max_wh_ratio`, matching PaddleOCR's `resize_norm_img`),
+/// then pads the right edge with zeros and normalizes pixels to `[-1, 1]`.
+#[cfg(feature = "use-opencv")]
+fn crnn_resize_norm_img(
+    img: &Mat,
+    img_c: usize,
+    img_h: usize,
+    _img_w: usize,
+    max_wh_ratio: f32,
+    input_color: crate::types::InputColor,
+    background: [u8; 3],
+) -> Result<Array3<f32>, EngineError> {
+    let img_width = (img_h as f32 * max_wh_ratio).round() as i32;
+
+    let h = img.rows();
+    let w = img.cols();
+    if h <= 0 || w <= 0 {
+        return Err(EngineError::Preprocess("invalid image size".to_string()));
+    }
 
-        let mut resized = Mat::default();
-        crate::image_impl::resize(
-            img,
-            &mut resized,
-            Size::new(resized_w, img_h as i32),
-            INTER_LINEAR,
-        )?;
-
-        let size = resized.size()?;
-        let h2 = size.height as usize;
-        let w2 = size.width as usize;
-
-        let mut out = Array3::<f32>::zeros((img_c, img_h, img_width as usize));
-
-        for y in 0..h2 {
-            for x in 0..w2.min(img_width as usize) {
-                let pix = resized.get_pixel(x as u32, y as u32);
-                let b = pix[0] as f32 / 255.0;
-                let g = pix[1] as f32 / 255.0;
-                let r = pix[2] as f32 / 255.0;
-
-                out[[0, y, x]] = (b - 0.5) / 0.5;
-                out[[1, y, x]] = (g - 0.5) / 0.5;
-                out[[2, y, x]] = (r - 0.5) / 0.5;
-            }
+    let ratio = w as f32 / h as f32;
+    let resized_w = if ((img_h as f32) * ratio).ceil() as i32 > img_width {
+        img_width
+    } else {
+        ((img_h as f32) * ratio).ceil() as i32
+    };
+
+    let mut resized = Mat::default();
+    imgproc::resize(
+        img,
+        &mut resized,
+        core::Size::new(resized_w, img_h as i32),
+        0.0,
+        0.0,
+        imgproc::INTER_LINEAR,
+    )?;
+
+    let size = resized.size()?;
+    let h2 = size.height as usize;
+    let w2 = size.width as usize;
+
+    // Create zero-padded array like Python: padding_im = np.zeros((img_channel, img_height, img_width))
+    // IMPORTANT: Use calculated img_width (from max_wh_ratio), not configured img_w!
+    let mut out = Array3::<f32>::zeros((img_c, img_h, img_width as usize));
+
+    // Only fill the resized portion: padding_im[:, :, 0:resized_w] = resized_image
+    // The rest remains zeros (padding on the right). Fetched a row at a time
+    // (one color-mode resolution instead of one per pixel) via `sample_rgb_row`.
+    let w_fill = w2.min(img_width as usize);
+    for y in 0..h2 {
+        let row = crate::image_impl::sample_rgb_row(&resized, y as i32, w_fill as i32, input_color, background)?;
+        for (x, [r, g, b]) in row.into_iter().enumerate() {
+            let b = b as f32 / 255.0;
+            let g = g as f32 / 255.0;
+            let r = r as f32 / 255.0;
+
+            out[[0, y, x]] = (b - 0.5) / 0.5;
+            out[[1, y, x]] = (g - 0.5) / 0.5;
+            out[[2, y, x]] = (r - 0.5) / 0.5;
         }
+    }
 
-        Ok(out)
+    Ok(out)
+}
+
+/// CRNN-style resize-and-normalize step shared by the recognizer.
+///
+/// Resizes `img` to `img_h` while keeping its aspect ratio (capped at the
+/// width implied by `max_wh_ratio`, matching PaddleOCR's `resize_norm_img`),
+/// then pads the right edge with zeros and normalizes pixels to `[-1, 1]`.
+#[cfg(not(feature = "use-opencv"))]
+fn crnn_resize_norm_img(
+    img: &Mat,
+    img_c: usize,
+    img_h: usize,
+    _img_w: usize,
+    max_wh_ratio: f32,
+    input_color: crate::types::InputColor,
+    background: [u8; 3],
+) -> Result<Array3<f32>, EngineError> {
+    let img_width = (img_h as f32 * max_wh_ratio).round() as i32;
+
+    let h = img.rows();
+    let w = img.cols();
+    if h <= 0 || w <= 0 {
+        return Err(EngineError::Preprocess("invalid image size".to_string()));
+    }
+
+    let ratio = w as f32 / h as f32;
+    let resized_w = if ((img_h as f32) * ratio).ceil() as i32 > img_width {
+        img_width
+    } else {
+        ((img_h as f32) * ratio).ceil() as i32
+    };
+
+    let mut resized = Mat::default();
+    crate::image_impl::resize(
+        img,
+        &mut resized,
+        Size::new(resized_w, img_h as i32),
+        INTER_LINEAR,
+    )?;
+
+    let size = resized.size()?;
+    let h2 = size.height as usize;
+    let w2 = size.width as usize;
+
+    let mut out = Array3::<f32>::zeros((img_c, img_h, img_width as usize));
+
+    // Fetched a row at a time (one color-mode resolution instead of one per
+    // pixel) via `sample_rgb_row`.
+    let w_fill = w2.min(img_width as usize);
+    for y in 0..h2 {
+        let row = crate::image_impl::sample_rgb_row(&resized, y as i32, w_fill as i32, input_color, background)?;
+        for (x, pix) in row.into_iter().enumerate() {
+            let b = pix[0] as f32 / 255.0;
+            let g = pix[1] as f32 / 255.0;
+            let r = pix[2] as f32 / 255.0;
+
+            out[[0, y, x]] = (b - 0.5) / 0.5;
+            out[[1, y, x]] = (g - 0.5) / 0.5;
+            out[[2, y, x]] = (r - 0.5) / 0.5;
+        }
     }
+
+    Ok(out)
 }
 
 fn has_chinese_char(text: &str) -> bool {
@@ -521,3 +943,60 @@ fn has_chinese_char(text: &str) -> bool {
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lm_with(alpha: f32, beta: f32) -> NgramLm {
+        let mut word_log_probs = std::collections::HashMap::new();
+        word_log_probs.insert("cat".to_string(), -1.0); // prob = 0.1
+        NgramLm {
+            word_log_probs,
+            unknown_log_prob: -10.0, // prob = 1e-10
+            alpha,
+            beta,
+        }
+    }
+
+    #[test]
+    fn test_word_boost_beta_is_additive_not_multiplicative() {
+        // A large beta, chosen so the old `prob.powf(alpha) + beta` bug
+        // would make an in-vocabulary word's boost (0.1 + 5.0) and an
+        // unknown word's boost (1e-10 + 5.0) nearly indistinguishable.
+        let lm = lm_with(1.0, 5.0);
+        let (common_factor, common_bonus) = lm.word_boost("cat");
+        let (garbage_factor, garbage_bonus) = lm.word_boost("not_a_real_word");
+
+        // beta must land as the same flat bonus regardless of word
+        // plausibility...
+        assert_eq!(common_bonus, garbage_bonus);
+        assert_eq!(common_bonus, 5.0);
+
+        // ...while the multiplicative LM factor alone still discriminates
+        // sharply between a plausible and an implausible word. A higher
+        // beta must not be able to erase this gap.
+        assert!(common_factor > garbage_factor * 1e6);
+    }
+
+    #[test]
+    fn test_higher_beta_does_not_let_garbage_word_outscore_real_word() {
+        // Two beams with identical pre-LM scores, one completing a word the
+        // LM knows, one completing gibberish.
+        let lm = lm_with(1.0, 5.0);
+
+        let mut common = BeamProb { p_b: 0.01, p_nb: 0.0 };
+        let (factor, bonus) = lm.word_boost("cat");
+        common.p_b = common.p_b * factor + bonus / 2.0;
+        common.p_nb = common.p_nb * factor + bonus / 2.0;
+
+        let mut garbage = BeamProb { p_b: 0.01, p_nb: 0.0 };
+        let (factor, bonus) = lm.word_boost("not_a_real_word");
+        garbage.p_b = garbage.p_b * factor + bonus / 2.0;
+        garbage.p_nb = garbage.p_nb * factor + bonus / 2.0;
+
+        // Raising beta adds the same flat amount to both, so it can't flip
+        // the ranking the LM factor already established.
+        assert!(common.total() > garbage.total());
+    }
+}
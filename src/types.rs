@@ -3,6 +3,13 @@ use std::path::PathBuf;
 #[derive(Clone, Copy, Debug)]
 pub enum LangRec {
     Ch,
+    En,
+    Japan,
+    Korean,
+    Latin,
+    /// Any other PP-OCR-compatible language: caller supplies the dictionary
+    /// via `RecConfig.rec_keys_path` (or `ClsConfig`/`DetConfig` equivalents).
+    Custom,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -27,11 +34,73 @@ pub enum TaskType {
     Rec,
 }
 
+/// How a decoded `Mat` should be interpreted before the det/rec
+/// normalization math runs. `Auto` (the default) inspects the underlying
+/// image's channel count/alpha on each fetch (once per row for the row-wise
+/// `sample_rgb_row` the det/rec passes use); the explicit variants
+/// override that detection for callers that already know their source
+/// format. Grayscale is broadcast to all three color planes; `Rgba`
+/// composites the alpha channel over a configurable background before
+/// normalization; palette images are expanded to RGB by the decoder before
+/// this ever sees them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputColor {
+    #[default]
+    Auto,
+    Gray,
+    Bgr,
+    Rgba,
+}
+
+/// A hardware backend to try for model inference. Providers are attempted in
+/// the order listed in `EngineConfig::execution_providers`; if one fails to
+/// initialize (missing driver, unsupported op, etc.) the session falls back
+/// to the next entry, and ultimately to `Cpu`.
+///
+/// Accelerator variants are feature-gated so a CPU-only build doesn't pull in
+/// their dependencies.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExecutionProvider {
+    Cpu,
+    #[cfg(feature = "cuda")]
+    Cuda { device_id: i32 },
+    #[cfg(feature = "tensorrt")]
+    TensorRt,
+    #[cfg(feature = "coreml")]
+    CoreMl,
+    #[cfg(feature = "directml")]
+    DirectMl,
+}
+
+/// Numeric precision MNN should compute in for a session, mirroring
+/// `mnn::PrecisionMode` so `types.rs` doesn't need the `mnn` crate visible.
+/// `engine::MnnSession::precision_mode_for` maps this onto the real type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrecisionMode {
+    Low,
+    Normal,
+    High,
+    Lossless,
+}
+
+/// Power/performance tradeoff MNN should target for a session, mirroring
+/// `mnn::PowerMode`. See `PrecisionMode` for why this isn't the `mnn` type
+/// directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerMode {
+    Low,
+    Normal,
+    High,
+}
+
 #[derive(Clone, Debug)]
 pub struct EngineConfig {
     pub intra_op_num_threads: i32,
     pub inter_op_num_threads: i32,
     pub enable_cpu_mem_arena: bool,
+    pub execution_providers: Vec<ExecutionProvider>,
+    pub precision_mode: PrecisionMode,
+    pub power_mode: PowerMode,
 }
 
 impl Default for EngineConfig {
@@ -40,11 +109,16 @@ impl Default for EngineConfig {
         let num_threads = std::thread::available_parallelism()
             .map(|n| n.get() as i32)
             .unwrap_or(4);
-        
+
         Self {
             intra_op_num_threads: num_threads,
             inter_op_num_threads: 1, // Keep inter-op at 1 for better cache locality
             enable_cpu_mem_arena: true, // Enable for better memory performance
+            execution_providers: vec![ExecutionProvider::Cpu],
+            // Matches the behavior this crate shipped with before these
+            // fields existed, so existing callers see no change by default.
+            precision_mode: PrecisionMode::High,
+            power_mode: PowerMode::High,
         }
     }
 }
@@ -67,6 +141,23 @@ pub struct DetConfig {
     pub unclip_ratio: f32,
     pub use_dilation: bool,
     pub score_mode: String,
+    pub box_type: String,
+    /// Trace box contours from the raw probability map via marching squares
+    /// instead of thresholding to a mask and running `find_contours`, giving
+    /// fractional-pixel box edges (see `marching_squares::marching_squares_supersampled`).
+    /// Off by default, matching this crate's pre-existing behavior.
+    pub sub_pixel_contours: bool,
+    /// Supersampling factor passed to `marching_squares_supersampled` when
+    /// `sub_pixel_contours` is set; `1` disables supersampling. Ignored
+    /// otherwise.
+    pub contour_precision: usize,
+    pub input_color: InputColor,
+    pub background: [u8; 3],
+    /// Worker cap for the rayon pool `DetPreProcess` uses to normalize rows
+    /// in parallel (behind the `parallel` feature). `0` means "let rayon's
+    /// global pool decide", matching `GlobalConfig::num_threads`'s sibling
+    /// knob for the det/rec stages themselves.
+    pub preprocess_threads: usize,
     pub engine_cfg: EngineConfig,
 }
 
@@ -89,13 +180,18 @@ impl DetConfig {
             unclip_ratio: 1.6,
             use_dilation: true,
             score_mode: "fast".to_string(),
+            box_type: "quad".to_string(),
+            sub_pixel_contours: false,
+            contour_precision: 1,
+            input_color: InputColor::Auto,
+            background: [255, 255, 255],
+            preprocess_threads: 0,
             engine_cfg: EngineConfig::default(),
         }
     }
 }
 
 #[derive(Clone, Debug)]
-#[allow(dead_code)] // Classification feature not yet implemented
 pub struct ClsConfig {
     pub engine_type: EngineType,
     pub lang_type: LangRec,
@@ -110,6 +206,24 @@ pub struct ClsConfig {
     pub engine_cfg: EngineConfig,
 }
 
+impl ClsConfig {
+    pub fn ppv5(model_path: PathBuf) -> Self {
+        Self {
+            engine_type: EngineType::OnnxRuntime,
+            lang_type: LangRec::Ch,
+            model_type: ModelType::Mobile,
+            ocr_version: OcrVersion::PpOcrV5,
+            task_type: TaskType::Cls,
+            model_path,
+            cls_image_shape: [3, 48, 192],
+            cls_batch_num: 6,
+            cls_thresh: 0.9,
+            label_list: vec!["0".to_string(), "180".to_string()],
+            engine_cfg: EngineConfig::default(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RecConfig {
     pub engine_type: EngineType,
@@ -121,26 +235,62 @@ pub struct RecConfig {
     pub rec_keys_path: Option<PathBuf>,
     pub rec_img_shape: [i32; 3],
     pub rec_batch_num: i32,
+    pub decode_mode: crate::rec::DecodeMode,
+    pub ctc_label: crate::rec::CtcLabelConfig,
+    pub input_color: InputColor,
+    pub background: [u8; 3],
+    /// Worker cap for the rayon pool used when normalizing a recognition
+    /// batch in parallel (behind the `parallel` feature). `0` means "let
+    /// rayon's global pool decide". See `DetConfig::preprocess_threads`.
+    pub preprocess_threads: usize,
     pub engine_cfg: EngineConfig,
 }
 
 impl RecConfig {
     pub fn ppv5(model_path: PathBuf) -> Self {
+        Self::ppv5_for_lang(LangRec::Ch, model_path, None)
+    }
+
+    /// Build a PP-OCRv5 recognition config for `lang`, filling in that
+    /// language's default character dictionary when `dict_path` is `None`.
+    /// `LangRec::Custom` requires an explicit `dict_path`.
+    pub fn ppv5_for_lang(lang: LangRec, model_path: PathBuf, dict_path: Option<PathBuf>) -> Self {
+        let rec_keys_path = dict_path.or_else(|| default_dict_path(lang));
+
         Self {
             engine_type: EngineType::OnnxRuntime,
-            lang_type: LangRec::Ch,
+            lang_type: lang,
             model_type: ModelType::Mobile,
             ocr_version: OcrVersion::PpOcrV5,
             task_type: TaskType::Rec,
             model_path,
-            rec_keys_path: None,
+            rec_keys_path,
             rec_img_shape: [3, 48, 320],
             rec_batch_num: 6,
+            decode_mode: crate::rec::DecodeMode::Greedy,
+            ctc_label: crate::rec::CtcLabelConfig::default(),
+            input_color: InputColor::Auto,
+            background: [255, 255, 255],
+            preprocess_threads: 0,
             engine_cfg: EngineConfig::default(),
         }
     }
 }
 
+/// Default PP-OCR dictionary file shipped alongside each language's model,
+/// following the naming PaddleOCR itself uses under `ppocr/utils/`.
+fn default_dict_path(lang: LangRec) -> Option<PathBuf> {
+    let name = match lang {
+        LangRec::Ch => "ppocr_keys_v1.txt",
+        LangRec::En => "en_dict.txt",
+        LangRec::Japan => "japan_dict.txt",
+        LangRec::Korean => "korean_dict.txt",
+        LangRec::Latin => "latin_dict.txt",
+        LangRec::Custom => return None,
+    };
+    Some(PathBuf::from(name))
+}
+
 #[derive(Clone, Debug)]
 pub struct GlobalConfig {
     pub text_score: f32,
@@ -153,10 +303,30 @@ pub struct GlobalConfig {
     pub min_side_len: f32,
     pub return_word_box: bool,
     pub return_single_char_box: bool,
+    /// Worker count for `RapidOcr::run_batch`'s thread crew. Defaults to the
+    /// detected core count, same auto-detection `EngineConfig` uses for
+    /// `intra_op_num_threads`.
+    pub num_threads: usize,
+    /// Reorder detected boxes into top-to-bottom, left-to-right reading
+    /// order before cropping, instead of leaving them in raw detector order.
+    /// Off by default so existing callers see no change in box/text
+    /// ordering. See `run_on_mat`'s line-grouping sort for the algorithm.
+    pub sort_boxes: bool,
+    /// Rectify each detected quad to an upright rectangle via perspective
+    /// warp before recognition (see `geometry::get_rotate_crop_image`),
+    /// instead of just cropping its axis-aligned bounding rect. On by
+    /// default, matching the behavior this crate shipped with before this
+    /// flag existed. Turn off to save the warp's cost when text is known to
+    /// already be axis-aligned.
+    pub rectify_quads: bool,
 }
 
 impl Default for GlobalConfig {
     fn default() -> Self {
+        let num_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4);
+
         Self {
             text_score: 0.5,
             use_det: true,
@@ -168,6 +338,9 @@ impl Default for GlobalConfig {
             min_side_len: 30.0,
             return_word_box: false,
             return_single_char_box: false,
+            num_threads,
+            sort_boxes: false,
+            rectify_quads: true,
         }
     }
 }
@@ -0,0 +1,136 @@
+//! Debug visualization: render detected boxes over the source image.
+//!
+//! Mirrors the `TextDetector`/`TextRecognizer` split between the OpenCV and
+//! pure-Rust backends — the OpenCV path uses `imgproc::polylines` with
+//! `LINE_AA`, the pure-Rust path uses `imageproc`'s anti-aliased line
+//! drawing over the same `Mat` abstraction the rest of the crate uses.
+
+#[cfg(feature = "use-opencv")]
+use opencv::{core, imgproc, prelude::*};
+
+#[cfg(feature = "use-opencv")]
+use opencv::core::Mat;
+
+#[cfg(not(feature = "use-opencv"))]
+use crate::image_impl::Mat;
+
+use crate::engine::EngineError;
+use crate::geometry::Quad;
+
+/// RGB color, 0-255 per channel.
+#[derive(Clone, Copy, Debug)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const GREEN: Color = Color(0, 255, 0);
+    pub const RED: Color = Color(255, 0, 0);
+}
+
+/// Draws detected text boxes as anti-aliased outlines over `img`.
+pub struct BoxVisualizer {
+    pub color: Color,
+    pub thickness: i32,
+}
+
+impl Default for BoxVisualizer {
+    fn default() -> Self {
+        Self {
+            color: Color::GREEN,
+            thickness: 2,
+        }
+    }
+}
+
+impl BoxVisualizer {
+    pub fn new(color: Color, thickness: i32) -> Self {
+        Self { color, thickness }
+    }
+
+    #[cfg(feature = "use-opencv")]
+    pub fn draw_boxes(&self, img: &Mat, boxes: &[Quad]) -> Result<Mat, EngineError> {
+        let mut out = img.clone();
+        let cv_color = core::Scalar::new(
+            self.color.2 as f64,
+            self.color.1 as f64,
+            self.color.0 as f64,
+            0.0,
+        );
+
+        for quad in boxes {
+            let pts: core::Vector<core::Point> = quad
+                .iter()
+                .map(|p| core::Point::new(p.x.round() as i32, p.y.round() as i32))
+                .collect();
+            let mut poly = core::Vector::<core::Vector<core::Point>>::new();
+            poly.push(pts);
+
+            imgproc::polylines(
+                &mut out,
+                &poly,
+                true,
+                cv_color,
+                self.thickness,
+                imgproc::LINE_AA,
+                0,
+            )?;
+        }
+
+        Ok(out)
+    }
+
+    #[cfg(not(feature = "use-opencv"))]
+    pub fn draw_boxes(&self, img: &Mat, boxes: &[Quad]) -> Result<Mat, EngineError> {
+        use imageproc::drawing::draw_antialiased_line_segment_mut;
+        use imageproc::pixelops::interpolate;
+        use image::Rgb;
+
+        let mut canvas = img.to_rgb8();
+        let rgb = Rgb([self.color.0, self.color.1, self.color.2]);
+
+        for quad in boxes {
+            for i in 0..4 {
+                let a = quad[i];
+                let b = quad[(i + 1) % 4];
+                // `imageproc` only draws 1px anti-aliased lines; approximate
+                // `thickness` by stacking the same line at small offsets.
+                for t in 0..self.thickness.max(1) {
+                    let off = t as f32;
+                    draw_antialiased_line_segment_mut(
+                        &mut canvas,
+                        (a.x.round() as i32, (a.y + off).round() as i32),
+                        (b.x.round() as i32, (b.y + off).round() as i32),
+                        rgb,
+                        interpolate,
+                    );
+                }
+            }
+        }
+
+        Ok(Mat::new(image::DynamicImage::ImageRgb8(canvas)))
+    }
+}
+
+/// Crops the axis-aligned bounding rectangle of each detected quad out of
+/// `img`, in the order the quads are given.
+#[cfg(not(feature = "use-opencv"))]
+#[allow(dead_code)]
+pub fn crop_boxes(img: &Mat, boxes: &[Quad]) -> Vec<Mat> {
+    let rgb = img.to_rgb8();
+    let (w, h) = (rgb.width() as f32, rgb.height() as f32);
+
+    boxes
+        .iter()
+        .map(|quad| {
+            let xmin = quad.iter().map(|p| p.x).fold(f32::INFINITY, f32::min).max(0.0);
+            let xmax = quad.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max).min(w - 1.0);
+            let ymin = quad.iter().map(|p| p.y).fold(f32::INFINITY, f32::min).max(0.0);
+            let ymax = quad.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max).min(h - 1.0);
+
+            let crop_w = (xmax - xmin).max(1.0).round() as u32;
+            let crop_h = (ymax - ymin).max(1.0).round() as u32;
+            let cropped = image::imageops::crop_imm(&rgb, xmin.round() as u32, ymin.round() as u32, crop_w, crop_h)
+                .to_image();
+            Mat::new(image::DynamicImage::ImageRgb8(cropped))
+        })
+        .collect()
+}